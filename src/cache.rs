@@ -0,0 +1,116 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Local cache of deployed models.
+//!
+//! The cache stores the hash of the content of every deployed model together
+//! with the identifier returned by the engine, so unchanged models
+//! do not have to be deployed again in the incremental mode. The hash of the model
+//! covers also hashes of imported models, so the model is deployed again
+//! when any of the models it imports has changed.
+
+use crate::definitions::{resolve_imports, ModelFile};
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Cached details of the deployed model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedModel {
+  /// Hash of the model content.
+  #[serde(rename = "hash")]
+  pub hash: String,
+  /// Identifier of the deployed model returned by the engine.
+  #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+}
+
+/// Cache of deployed models, indexed by model tags.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeploymentCache {
+  #[serde(rename = "models")]
+  models: BTreeMap<String, CachedModel>,
+}
+
+impl DeploymentCache {
+  /// Loads the cache from file, missing file results in empty cache.
+  pub fn load(file_name: &str) -> Result<Self> {
+    if !Path::new(file_name).exists() {
+      return Ok(Self::default());
+    }
+    let content =
+      fs::read_to_string(file_name).map_err(|reason| ReadingFileFailed(format!("{}: {}", file_name, reason)))?;
+    serde_json::from_str(&content).map_err(|reason| ReadingFileFailed(format!("{}: {}", file_name, reason)))
+  }
+
+  /// Saves the cache to file.
+  pub fn save(&self, file_name: &str) -> Result<()> {
+    let content = serde_json::to_string_pretty(self).map_err(|reason| SerializingFailed(format!("{}", reason)))?;
+    fs::write(file_name, content)?;
+    Ok(())
+  }
+
+  /// Returns the cached model with specified tag, only when the content hash did not change.
+  pub fn get(&self, tag: &str, hash: &str) -> Option<&CachedModel> {
+    self.models.get(tag).filter(|cached| cached.hash == hash)
+  }
+
+  /// Stores the deployed model in cache.
+  pub fn insert(&mut self, tag: &str, hash: &str, id: Option<String>) {
+    self.models.insert(
+      tag.to_string(),
+      CachedModel {
+        hash: hash.to_string(),
+        id,
+      },
+    );
+  }
+
+  /// Removes the model from cache.
+  pub fn remove(&mut self, tag: &str) {
+    self.models.remove(tag);
+  }
+}
+
+/// Calculates the hash of the model content using 64-bit FNV-1a algorithm.
+/// This hash is not cryptographic, it is used only for detecting changes in models.
+pub fn content_hash(content: &str) -> String {
+  let hash = content.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+  });
+  format!("{:016x}", hash)
+}
+
+/// Calculates hashes of models given in the order of deployment, indexed by model file names.
+/// The hash of every model is calculated from the model content and hashes of imported models.
+pub fn model_hashes(models: &[ModelFile]) -> Result<BTreeMap<String, String>> {
+  let mut hashes: BTreeMap<String, String> = BTreeMap::new();
+  for model in models {
+    let mut content = fs::read_to_string(&model.file_name)?;
+    for imported_model in resolve_imports(models, model)? {
+      if let Some(hash) = hashes.get(&imported_model.file_name) {
+        content.push_str(hash);
+      }
+    }
+    hashes.insert(model.file_name.clone(), content_hash(&content));
+  }
+  Ok(hashes)
+}
@@ -0,0 +1,229 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Comparison of actual values returned by the engine with expected values.
+
+use crate::dto::{ComponentDto, ListDto, SimpleDto, ValueDto};
+use crate::errors::{Result, RunnerError};
+use regex::Regex;
+
+lazy_static! {
+  /// Pattern of the decimal number.
+  static ref DECIMAL: Regex = Regex::new(r"^([+-]?)(\d*)(?:\.(\d*))?$").unwrap();
+  /// Pattern of the date, optionally followed by timezone or time.
+  static ref DATE_TIME: Regex = Regex::new(r"^(-?\d{4,}-\d{2}-\d{2})(?:(Z|[+-]\d{2}:\d{2})|T(.+))?$").unwrap();
+}
+
+/// Numeric types of simple values.
+pub const NUMERIC_TYPES: [&str; 10] = [
+  "decimal",
+  "double",
+  "float",
+  "integer",
+  "long",
+  "int",
+  "short",
+  "byte",
+  "nonNegativeInteger",
+  "positiveInteger",
+];
+
+/// Integer types of simple values.
+const INTEGER_TYPES: [&str; 7] = [
+  "integer",
+  "long",
+  "int",
+  "short",
+  "byte",
+  "nonNegativeInteger",
+  "positiveInteger",
+];
+
+/// Options for comparing values.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CompareOptions {
+  /// Flag indicating if components of contexts must appear in the same order.
+  /// By default the order of components is not significant.
+  pub ordered_components: bool,
+  /// Flag indicating if types of actual values should be recovered from expected values
+  /// before comparison, required when values returned by the engine carry no type information.
+  pub recover_types: bool,
+}
+
+/// Compares actual value with expected value, returns `true` when values are equal.
+pub fn compare_values(actual: &ValueDto, expected: &ValueDto, options: &CompareOptions) -> bool {
+  actual.simple == expected.simple
+    && match (&actual.components, &expected.components) {
+      (Some(a), Some(e)) => compare_components(a, e, options),
+      (None, None) => true,
+      _ => false,
+    }
+    && match (&actual.list, &expected.list) {
+      (Some(a), Some(e)) => compare_lists(a, e, options),
+      (None, None) => true,
+      _ => false,
+    }
+}
+
+/// Compares lists, the order of list items is always significant.
+fn compare_lists(actual: &ListDto, expected: &ListDto, options: &CompareOptions) -> bool {
+  actual.nil == expected.nil
+    && actual.items.len() == expected.items.len()
+    && actual
+      .items
+      .iter()
+      .zip(expected.items.iter())
+      .all(|(a, e)| compare_values(a, e, options))
+}
+
+/// Compares components of contexts. When the order of components is not significant,
+/// components are compared after sorting by name; the relative order of components
+/// having the same name is preserved, so duplicated names are compared in document order.
+fn compare_components(actual: &[ComponentDto], expected: &[ComponentDto], options: &CompareOptions) -> bool {
+  if actual.len() != expected.len() {
+    return false;
+  }
+  let mut actual: Vec<&ComponentDto> = actual.iter().collect();
+  let mut expected: Vec<&ComponentDto> = expected.iter().collect();
+  if !options.ordered_components {
+    actual.sort_by(|a, b| a.name.cmp(&b.name));
+    expected.sort_by(|a, b| a.name.cmp(&b.name));
+  }
+  actual
+    .iter()
+    .zip(expected.iter())
+    .all(|(a, e)| compare_component(a, e, options))
+}
+
+/// Compares single components.
+fn compare_component(actual: &ComponentDto, expected: &ComponentDto, options: &CompareOptions) -> bool {
+  actual.name == expected.name
+    && actual.nil == expected.nil
+    && match (&actual.value, &expected.value) {
+      (Some(a), Some(e)) => compare_values(a, e, options),
+      (None, None) => true,
+      _ => false,
+    }
+}
+
+/// Casts the value to the type specified in `cast` attribute of the result node.
+///
+/// Simple values are converted to the text representation of the target type:
+/// numbers are normalized (no sign `+`, no leading and trailing zeros), dates are
+/// extracted from date and time values, times are extracted from date and time values,
+/// booleans are normalized to `true` or `false`. Items of lists are casted one by one.
+/// Contexts can not be casted.
+pub fn cast_value(value: &ValueDto, cast: &str) -> Result<ValueDto> {
+  if let Some(simple) = &value.simple {
+    Ok(ValueDto {
+      simple: Some(cast_simple(simple, cast)?),
+      ..Default::default()
+    })
+  } else if let Some(list) = &value.list {
+    Ok(ValueDto {
+      list: Some(ListDto {
+        items: list
+          .items
+          .iter()
+          .map(|item| cast_value(item, cast))
+          .collect::<Result<_>>()?,
+        nil: list.nil,
+      }),
+      ..Default::default()
+    })
+  } else if value.components.is_some() {
+    Err(RunnerError::CastFailed(format!(
+      "context can not be casted to '{}'",
+      cast
+    )))
+  } else {
+    Ok(ValueDto::default())
+  }
+}
+
+/// Casts simple value to specified type.
+fn cast_simple(simple: &SimpleDto, cast: &str) -> Result<SimpleDto> {
+  let text = match &simple.text {
+    Some(text) if !simple.nil => Some(cast_text(text.trim(), local_name(cast))?),
+    _ => None,
+  };
+  Ok(SimpleDto {
+    typ: Some(cast.to_string()),
+    text,
+    nil: simple.nil,
+  })
+}
+
+/// Casts textual representation of the value to type with specified local name.
+fn cast_text(text: &str, typ: &str) -> Result<String> {
+  let failed = || RunnerError::CastFailed(format!("value '{}' can not be casted to '{}'", text, typ));
+  if NUMERIC_TYPES.contains(&typ) {
+    let number = normalize_decimal(text)
+      .or_else(|| text.parse::<f64>().ok().and_then(|n| normalize_decimal(&n.to_string())))
+      .ok_or_else(failed)?;
+    if INTEGER_TYPES.contains(&typ) && number.contains('.') {
+      return Err(failed());
+    }
+    return Ok(number);
+  }
+  match typ {
+    "date" => DATE_TIME
+      .captures(text)
+      .map(|captures| match captures.get(2) {
+        Some(timezone) => format!("{}{}", &captures[1], timezone.as_str()),
+        None => captures[1].to_string(),
+      })
+      .ok_or_else(failed),
+    "time" => match DATE_TIME.captures(text) {
+      Some(captures) => captures.get(3).map(|time| time.as_str().to_string()).ok_or_else(failed),
+      None => Ok(text.to_string()),
+    },
+    "boolean" => match text {
+      "true" | "1" => Ok("true".to_string()),
+      "false" | "0" => Ok("false".to_string()),
+      _ => Err(failed()),
+    },
+    _ => Ok(text.to_string()),
+  }
+}
+
+/// Normalizes the textual representation of decimal number,
+/// returns `None` when the text is not a decimal number.
+pub fn normalize_decimal(text: &str) -> Option<String> {
+  let captures = DECIMAL.captures(text)?;
+  let fraction = captures.get(3).map_or("", |f| f.as_str());
+  if captures[2].is_empty() && fraction.is_empty() {
+    return None;
+  }
+  let integer = captures[2].trim_start_matches('0');
+  let fraction = fraction.trim_end_matches('0');
+  let integer = if integer.is_empty() { "0" } else { integer };
+  let sign = if &captures[1] == "-" && (integer != "0" || !fraction.is_empty()) {
+    "-"
+  } else {
+    ""
+  };
+  if fraction.is_empty() {
+    Some(format!("{}{}", sign, integer))
+  } else {
+    Some(format!("{}{}.{}", sign, integer, fraction))
+  }
+}
+
+/// Returns the local name of the type, without namespace prefix.
+pub fn local_name(typ: &str) -> &str {
+  typ.rsplit(':').next().unwrap_or(typ)
+}
@@ -1,30 +1,271 @@
+//! Configuration of the runner, read from YAML file.
+
+use crate::encoding::ValueEncoding;
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use std::collections::BTreeMap;
+
 /// Runner configuration parameters.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigurationParams {
   /// Path to directory containing test cases.
   pub test_cases_dir_path: String,
   /// Pattern for matching test file names.
-  /// Only files whose name matches the pattern will be processed.
+  /// Only test files whose name matches the pattern will be processed,
+  /// and only models referenced by these files (and models they import) will be deployed.
   pub file_name_pattern: String,
+  /// Optional command (followed by arguments) starting the engine as a subprocess.
+  /// When specified, the engine is accessed via standard input and output instead of REST endpoints.
+  #[serde(default)]
+  pub engine_command: Option<Vec<String>>,
+  /// Optional timeout of a single request sent to the engine process, in milliseconds.
+  #[serde(default)]
+  pub engine_timeout_ms: Option<u64>,
+  /// Optional directory where requests sent to the engine and received responses are recorded.
+  /// Values of configured HTTP headers (including authorization) are hidden in recordings.
+  #[serde(default)]
+  pub record_dir_path: Option<String>,
+  /// Optional directory with recorded requests and responses, replayed instead of running the engine.
+  #[serde(default)]
+  pub replay_dir_path: Option<String>,
+  /// Parameters of HTTP requests sent to REST services.
+  #[serde(default)]
+  pub http: HttpParams,
+  /// Mapping of requests and responses for engines with REST API different from the default one.
+  #[serde(default)]
+  pub mapping: MappingParams,
   /// URL to REST service where dmn definitions will be deployed.
+  #[serde(default)]
   pub deploy_url: String,
+  /// Optional URL to REST service where deployed dmn definitions will be removed.
+  /// When specified, models deployed from a directory are removed after all tests
+  /// from this directory are processed.
+  #[serde(default)]
+  pub undeploy_url: Option<String>,
+  /// Optional URL to REST service verifying if the model deployed earlier is still available.
+  /// Used in incremental deployment to confirm the cached model identifier.
+  #[serde(default)]
+  pub verify_url: Option<String>,
+  /// Optional URL to REST service checking if the engine is up and ready,
+  /// the engine is healthy when the service responds with success status.
+  #[serde(default)]
+  pub health_url: Option<String>,
+  /// Parameters of checking the engine health before and during the run.
+  #[serde(default)]
+  pub health: HealthParams,
+  /// Optional path to the file where hashes and identifiers of deployed models are cached.
+  #[serde(default)]
+  pub deployment_cache_file_path: Option<String>,
+  /// Flag indicating if only models changed since the previous run should be deployed.
+  /// Requires the deployment cache file, models are not undeployed in this mode.
+  #[serde(default)]
+  pub incremental_deployment: bool,
+  /// Encoding of values exchanged with the engine, `typed` when not specified.
+  #[serde(default)]
+  pub value_encoding: ValueEncoding,
   /// URL to REST service where dmn definitions will be evaluated.
+  #[serde(default)]
   pub evaluate_url: String,
   /// Path to write csv report file.
   pub report_file_path: String,
+  /// Optional path to write csv report file with deployment times of models.
+  #[serde(default)]
+  pub deployment_report_file_path: Option<String>,
   /// Flag indicating if testing should immediately stop when a test fails.
   pub stop_on_failure: bool,
+  /// Conditions stopping the run, or a single test file, before all tests are executed.
+  #[serde(default)]
+  pub fail_fast: FailFastParams,
+  /// Flag indicating if testing should continue when deploying a model fails.
+  /// Tests of models that failed to parse or deploy, models with missing or cyclic imports,
+  /// and models importing such models, are reported as skipped.
+  #[serde(default)]
+  pub continue_on_deployment_failure: bool,
+  /// Flag indicating if components of contexts should be compared in document order.
+  /// When not specified, the order of components is not significant.
+  #[serde(default)]
+  pub ordered_components: bool,
+  /// Optional path to the file where outcomes of completed tests are saved as the run proceeds,
+  /// the interrupted run may be resumed from this checkpoint.
+  #[serde(default)]
+  pub checkpoint_file_path: Option<String>,
+  /// Number of the slowest tests printed in the summary of the run, 10 when not specified.
+  #[serde(default = "default_slowest_tests")]
+  pub slowest_tests: usize,
+  /// Optional parameters of the benchmark, used when the runner is started in benchmark mode.
+  #[serde(default)]
+  pub benchmark: Option<BenchmarkParams>,
+}
+
+/// Parameters of HTTP requests sent to REST services,
+/// used to access secured engine deployments.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct HttpParams {
+  /// Additional headers sent with every request.
+  #[serde(default)]
+  pub headers: BTreeMap<String, String>,
+  /// Optional token sent in `Authorization: Bearer` header.
+  #[serde(default)]
+  pub bearer_token: Option<String>,
+  /// Optional user name sent in `Authorization: Basic` header.
+  #[serde(default)]
+  pub basic_auth_user: Option<String>,
+  /// Optional password sent in `Authorization: Basic` header.
+  #[serde(default)]
+  pub basic_auth_password: Option<String>,
+  /// Optional path to client certificate with private key, in PKCS#12 format.
+  #[serde(default)]
+  pub client_identity_file_path: Option<String>,
+  /// Optional password protecting the client certificate.
+  #[serde(default)]
+  pub client_identity_password: Option<String>,
+  /// Optional path to additional trusted CA certificate, in PEM format.
+  #[serde(default)]
+  pub ca_certificate_file_path: Option<String>,
+  /// Flag indicating if invalid server certificates should be accepted, use for testing only.
+  #[serde(default)]
+  pub accept_invalid_certificates: bool,
+  /// Optional timeout of a single request, in milliseconds.
+  #[serde(default)]
+  pub timeout_ms: Option<u64>,
+}
+
+/// Parameters of the benchmark measuring latency and throughput of evaluations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkParams {
+  /// Number of evaluations of every result node before measuring, results are discarded.
+  #[serde(default)]
+  pub warmup_iterations: usize,
+  /// Number of measured evaluations of every result node.
+  pub iterations: usize,
+  /// Number of evaluations sent concurrently, 1 when not specified.
+  #[serde(default = "default_concurrency")]
+  pub concurrency: usize,
+  /// Path to write benchmark report file.
+  pub report_file_path: String,
+  /// Format of the benchmark report, `csv` when not specified.
+  #[serde(default)]
+  pub report_format: BenchmarkFormat,
+}
+
+/// Returns the default number of the slowest tests printed in the summary.
+fn default_slowest_tests() -> usize {
+  10
+}
+
+/// Returns the default number of concurrent evaluations in benchmark.
+fn default_concurrency() -> usize {
+  1
+}
+
+/// Format of the benchmark report.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BenchmarkFormat {
+  /// Lines with comma separated values, preceded by the header line.
+  #[default]
+  #[serde(rename = "csv")]
+  Csv,
+  /// Single JSON document.
+  #[serde(rename = "json")]
+  Json,
+}
+
+/// Parameters of checking the engine health.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct HealthParams {
+  /// Optional maximum time to wait until the engine is ready, in milliseconds.
+  /// When not specified, the engine must be ready when the run starts.
+  #[serde(default)]
+  pub ready_timeout_ms: Option<u64>,
+  /// Optional interval between health checks while waiting for the engine, in milliseconds.
+  #[serde(default)]
+  pub check_interval_ms: Option<u64>,
+  /// Action taken when the engine is not reachable during the run.
+  #[serde(default)]
+  pub on_engine_down: EngineDownAction,
+}
+
+/// Action taken when evaluation fails because the engine is not reachable
+/// and the following health check confirms that the engine is down.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EngineDownAction {
+  /// Failure is reported and the run continues.
+  #[default]
+  #[serde(rename = "report")]
+  Report,
+  /// Run is paused until the engine is ready again, deployed models are deployed again
+  /// and the failed evaluation is repeated. The run is aborted when the engine is not ready
+  /// within the ready timeout, or when the models can not be deployed again.
+  #[serde(rename = "pause")]
+  Pause,
+  /// Run is aborted.
+  #[serde(rename = "abort")]
+  Abort,
+}
+
+/// Conditions stopping the run before all tests are executed.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FailFastParams {
+  /// Optional number of failed tests stopping the run.
+  #[serde(default)]
+  pub max_failures: Option<u64>,
+  /// Optional percentage of failed tests stopping the run when exceeded.
+  #[serde(default)]
+  pub max_failure_rate: Option<f64>,
+  /// Minimal number of executed tests before the failure rate is checked.
+  #[serde(default)]
+  pub min_tests_for_failure_rate: u64,
+  /// Flag indicating if the run should stop on the first transport error (engine not reachable).
+  #[serde(default)]
+  pub stop_on_transport_error: bool,
+  /// Flag indicating if remaining tests from the test file should be skipped after the first failure,
+  /// tests from other files are still executed.
+  #[serde(default)]
+  pub stop_file_on_failure: bool,
+}
+
+/// Mappings of requests and responses of REST operations.
+/// Operations without mapping use default requests and responses.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MappingParams {
+  /// Mapping of the deployment operation.
+  #[serde(default)]
+  pub deploy: Option<OperationMapping>,
+  /// Mapping of the evaluation operation.
+  #[serde(default)]
+  pub evaluate: Option<OperationMapping>,
+  /// Mapping of the operation removing deployed models.
+  #[serde(default)]
+  pub undeploy: Option<OperationMapping>,
+  /// Mapping of the operation verifying deployed models.
+  #[serde(default)]
+  pub verify: Option<OperationMapping>,
+}
+
+/// Mapping of a single REST operation.
+///
+/// Request template and operation URL may contain placeholders `${/json/pointer}`
+/// replaced with values pointed in default request parameters, values substituted in URL are percent-encoded.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct OperationMapping {
+  /// Optional HTTP method, `POST` when not specified.
+  #[serde(default)]
+  pub method: Option<String>,
+  /// Optional template of the request body, default parameters are sent when not specified.
+  #[serde(default)]
+  pub request: Option<serde_json::Value>,
+  /// Optional JSON pointer to the result in the response, `/data` when not specified.
+  #[serde(default)]
+  pub result_pointer: Option<String>,
+  /// Optional JSON pointer to errors in the response, `/errors` when not specified.
+  #[serde(default)]
+  pub errors_pointer: Option<String>,
 }
 
-pub fn get() -> ConfigurationParams {
-  let args: Vec<String> = std::env::args().collect();
-  let cfg_file_name = if args.len() == 2 {
-    args[1].as_str()
-  } else {
-    "runner.yml"
-  };
-  let err_read = format!("reading configuration file '{}' failed", cfg_file_name);
-  let file_content = std::fs::read_to_string(cfg_file_name).expect(&err_read);
-  let err_parse = format!("parsing configuration file '{}' failed", cfg_file_name);
-  serde_yaml::from_str(&file_content).expect(&err_parse)
+/// Reads configuration from the YAML file with specified name.
+pub fn load(file_name: &str) -> Result<ConfigurationParams> {
+  let content = std::fs::read_to_string(file_name)
+    .map_err(|reason| ReadingFileFailed(format!("configuration file '{}': {}", file_name, reason)))?;
+  serde_yaml::from_str(&content)
+    .map_err(|reason| ParsingYAMLFailed(format!("configuration file '{}': {}", file_name, reason)))
 }
@@ -0,0 +1,311 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! DMN™ model definitions and the order of their deployment.
+//!
+//! Models may import other models, so the imported models must be deployed
+//! before the models importing them. Only the namespace of the model and the
+//! namespaces of imported DMN™ models are read from `*.dmn` files.
+
+use crate::errors::RunnerError::*;
+use crate::errors::{Result, RunnerError};
+use crate::runner::{dir_name, file_name};
+use roxmltree::Node;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::read_to_string;
+use std::path::Path;
+
+const NODE_DEFINITIONS: &str = "definitions";
+const NODE_IMPORT: &str = "import";
+
+const ATTR_IMPORT_TYPE: &str = "importType";
+const ATTR_NAMESPACE: &str = "namespace";
+
+/// Fragment of the `importType` attribute value identifying imported DMN™ models.
+const DMN_IMPORT_TYPE: &str = "/DMN/";
+
+/// DMN™ model file with its namespace and namespaces of imported models.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFile {
+  /// Name of the file containing the model.
+  pub file_name: String,
+  /// Namespace of the model.
+  pub namespace: String,
+  /// Namespaces of imported DMN™ models.
+  pub imports: Vec<String>,
+}
+
+/// Parses the model file.
+pub fn parse_model_file(file_name: &str) -> Result<ModelFile> {
+  match read_to_string(file_name) {
+    Ok(content) => parse_model(file_name, &content),
+    Err(reason) => Err(ReadingFileFailed(format!("{}", reason))),
+  }
+}
+
+/// Parses the model from string, `file_name` is the name of the file the content was read from.
+pub fn parse_model(file_name: &str, content: &str) -> Result<ModelFile> {
+  match roxmltree::Document::parse(content) {
+    Ok(document) => {
+      let definitions_node = document.root_element();
+      if definitions_node.tag_name().name() != NODE_DEFINITIONS {
+        return Err(XmlExpectedMandatoryNode(NODE_DEFINITIONS.to_string()));
+      }
+      Ok(ModelFile {
+        file_name: file_name.to_string(),
+        namespace: definitions_node
+          .attribute(ATTR_NAMESPACE)
+          .ok_or_else(|| XmlExpectedMandatoryAttribute(ATTR_NAMESPACE.to_string()))?
+          .to_string(),
+        imports: parse_imports(&definitions_node),
+      })
+    }
+    Err(reason) => Err(ParsingXMLFailed(format!("{}", reason))),
+  }
+}
+
+/// Parses namespaces of imported DMN™ models, imports of other types (XSD, PMML) are skipped.
+fn parse_imports(node: &Node) -> Vec<String> {
+  node
+    .children()
+    .filter(|n| n.tag_name().name() == NODE_IMPORT)
+    .filter(|n| {
+      n.attribute(ATTR_IMPORT_TYPE)
+        .map_or(true, |t| t.contains(DMN_IMPORT_TYPE))
+    })
+    .filter_map(|n| n.attribute(ATTR_NAMESPACE).map(|namespace| namespace.to_string()))
+    .collect()
+}
+
+/// Parses model files with specified names together with all model files they import, directly or indirectly.
+///
+/// Other model files are parsed only when the imported namespace is not defined by already parsed models
+/// from the directory of the importing model, first the remaining files from that directory are parsed,
+/// then all remaining files. Parsed models are returned in the same order as they appear in `dmn_files`,
+/// together with reasons of failures of parsing all model files that were attempted to be parsed.
+pub fn parse_required_models(
+  dmn_files: &[String],
+  file_names: &BTreeSet<String>,
+) -> (Vec<ModelFile>, BTreeMap<String, String>) {
+  let mut parsed: BTreeMap<&str, Result<ModelFile>> = BTreeMap::new();
+  let mut pending: Vec<&str> = dmn_files
+    .iter()
+    .map(String::as_str)
+    .filter(|dmn_file| file_names.contains(*dmn_file))
+    .collect();
+  while !pending.is_empty() {
+    for dmn_file in pending.drain(..) {
+      parsed.entry(dmn_file).or_insert_with(|| parse_model_file(dmn_file));
+    }
+    let models: Vec<&ModelFile> = parsed.values().flatten().collect();
+    for model in &models {
+      let dir = dir_name(&model.file_name);
+      for namespace in &model.imports {
+        if !models
+          .iter()
+          .any(|m| m.namespace == *namespace && dir_name(&m.file_name) == dir)
+        {
+          let unparsed = dmn_files
+            .iter()
+            .map(String::as_str)
+            .filter(|dmn_file| !parsed.contains_key(dmn_file) && !pending.contains(dmn_file));
+          let mut next: Vec<&str> = unparsed.clone().filter(|dmn_file| dir_name(dmn_file) == dir).collect();
+          if next.is_empty() {
+            next = unparsed.collect();
+          }
+          pending.append(&mut next);
+        }
+      }
+    }
+  }
+  let mut models = vec![];
+  let mut failures = BTreeMap::new();
+  for dmn_file in dmn_files {
+    match parsed.remove(dmn_file.as_str()) {
+      Some(Ok(model)) => models.push(model),
+      Some(Err(reason)) => {
+        failures.insert(dmn_file.clone(), format!("{:?}", reason));
+      }
+      None => {}
+    }
+  }
+  (models, failures)
+}
+
+/// Returns the unique tag of the model deployed from specified directory.
+/// The tag is built from the path of the directory relative to the root directory
+/// of test cases and the model name, so models having the same file name
+/// but placed in different directories get different tags.
+pub fn model_tag(root_dir: &Path, dir: &str, model_name: &str) -> String {
+  let dir = Path::new(dir);
+  dir
+    .strip_prefix(root_dir)
+    .unwrap_or(dir)
+    .join(model_name)
+    .display()
+    .to_string()
+}
+
+/// Selects models with specified file names together with all models they import, directly or indirectly.
+/// Selected models are returned in the same order as they appear in `models`.
+pub fn required_models(models: &[ModelFile], file_names: &BTreeSet<String>) -> Result<Vec<ModelFile>> {
+  let mut required: BTreeSet<&str> = BTreeSet::new();
+  let mut pending: Vec<&ModelFile> = models.iter().filter(|m| file_names.contains(&m.file_name)).collect();
+  while let Some(model) = pending.pop() {
+    if required.insert(model.file_name.as_str()) {
+      for namespace in &model.imports {
+        pending.push(resolve_import(models, model, namespace)?);
+      }
+    }
+  }
+  Ok(
+    models
+      .iter()
+      .filter(|m| required.contains(m.file_name.as_str()))
+      .cloned()
+      .collect(),
+  )
+}
+
+/// Returns reasons why models can not be deployed, keyed by model file name.
+///
+/// Models can not be deployed when they import namespaces not defined in any model,
+/// when their imports are cyclic, or when they import such models, directly or indirectly.
+pub fn invalid_models(models: &[ModelFile]) -> BTreeMap<String, String> {
+  let mut invalid = BTreeMap::new();
+  for model in models {
+    if let Err(reason) = resolve_imports(models, model) {
+      invalid.insert(model.file_name.clone(), format!("{:?}", reason));
+    }
+  }
+  loop {
+    let mut importing = BTreeMap::new();
+    for model in models.iter().filter(|m| !invalid.contains_key(&m.file_name)) {
+      let imported = resolve_imports(models, model).unwrap_or_default();
+      if let Some(imported) = imported.iter().find(|i| invalid.contains_key(&i.file_name)) {
+        importing.insert(
+          model.file_name.clone(),
+          format!(
+            "imported model '{}' can not be deployed",
+            file_name(&imported.file_name)
+          ),
+        );
+      }
+    }
+    if importing.is_empty() {
+      break;
+    }
+    invalid.append(&mut importing);
+  }
+  let valid: Vec<ModelFile> = models
+    .iter()
+    .filter(|m| !invalid.contains_key(&m.file_name))
+    .cloned()
+    .collect();
+  if let Ok((_, cyclic)) = dependency_order(&valid) {
+    let reason = format!("{:?}", cyclic_imports(&cyclic));
+    for name in cyclic {
+      invalid.insert(name, reason.clone());
+    }
+  }
+  invalid
+}
+
+/// Orders models in a way that every imported model precedes the models importing it.
+///
+/// Models not depending on each other remain in the order of file names.
+/// Missing imports and cyclic imports are reported as errors.
+pub fn deployment_order(models: &[ModelFile]) -> Result<Vec<ModelFile>> {
+  let (ordered, cyclic) = dependency_order(models)?;
+  if cyclic.is_empty() {
+    Ok(ordered)
+  } else {
+    Err(cyclic_imports(&cyclic))
+  }
+}
+
+/// Orders models like [deployment_order], models that can not be ordered because of cyclic imports
+/// are returned separately, as file names.
+fn dependency_order(models: &[ModelFile]) -> Result<(Vec<ModelFile>, Vec<String>)> {
+  let mut dependencies: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+  for model in models {
+    let mut imported = BTreeSet::new();
+    for namespace in &model.imports {
+      imported.insert(resolve_import(models, model, namespace)?.file_name.as_str());
+    }
+    imported.remove(model.file_name.as_str());
+    dependencies.insert(model.file_name.as_str(), imported);
+  }
+  let mut ordered: Vec<ModelFile> = vec![];
+  let mut deployed: BTreeSet<&str> = BTreeSet::new();
+  while deployed.len() < dependencies.len() {
+    let ready = dependencies
+      .iter()
+      .find(|(name, imported)| !deployed.contains(*name) && imported.iter().all(|i| deployed.contains(i)))
+      .map(|(name, _)| *name);
+    match ready {
+      Some(name) => {
+        deployed.insert(name);
+        if let Some(model) = models.iter().find(|m| m.file_name == name) {
+          ordered.push(model.clone());
+        }
+      }
+      None => {
+        let cyclic = dependencies
+          .keys()
+          .filter(|name| !deployed.contains(*name))
+          .map(|name| name.to_string())
+          .collect();
+        return Ok((ordered, cyclic));
+      }
+    }
+  }
+  Ok((ordered, vec![]))
+}
+
+/// Returns the error reporting cyclic imports of models with specified file names.
+fn cyclic_imports(file_names: &[String]) -> RunnerError {
+  let names: Vec<String> = file_names.iter().map(|name| file_name(name)).collect();
+  CyclicImports(names.join(", "))
+}
+
+/// Returns models imported by specified model, resolved the same way as in [deployment_order].
+pub fn resolve_imports<'a>(models: &'a [ModelFile], model: &ModelFile) -> Result<Vec<&'a ModelFile>> {
+  model
+    .imports
+    .iter()
+    .map(|namespace| resolve_import(models, model, namespace))
+    .collect()
+}
+
+/// Resolves the imported namespace to the model from the same directory as the importing model,
+/// and when not found there, to the model from any other directory.
+fn resolve_import<'a>(models: &'a [ModelFile], model: &ModelFile, namespace: &str) -> Result<&'a ModelFile> {
+  let candidates: Vec<&ModelFile> = models.iter().filter(|m| m.namespace == namespace).collect();
+  let dir = dir_name(&model.file_name);
+  match candidates
+    .iter()
+    .find(|m| dir_name(&m.file_name) == dir)
+    .or_else(|| candidates.first())
+  {
+    Some(imported_model) => Ok(imported_model),
+    None => Err(ImportNotFound(format!(
+      "model '{}' imports namespace '{}' not defined in any model",
+      file_name(&model.file_name),
+      namespace
+    ))),
+  }
+}
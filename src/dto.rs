@@ -14,53 +14,134 @@
  *  limitations under the License.
  */
 
-//!
+//! Data transfer objects.
 
-use crate::model::{Component, InputNode, List, Simple, Value};
+use crate::model::{Component, InputNode, List, ResultNode, Simple, TestCase, TestCaseType, TestCases, Value};
 
+/// Data transfer object for the actual value returned by the engine, the same as [ExpectedValueDto].
 pub type ActualValueDto = ExpectedValueDto;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Data transfer object for test cases stored in JSON or YAML file.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TestCasesDto {
+  /// Optional name of the model file the test cases are defined for.
+  #[serde(rename = "modelName", skip_serializing_if = "Option::is_none")]
+  pub model_name: Option<String>,
+  /// Labels of test cases.
+  #[serde(rename = "labels", default, skip_serializing_if = "Vec::is_empty")]
+  pub labels: Vec<String>,
+  /// Test cases.
+  #[serde(rename = "testCases", default)]
+  pub test_cases: Vec<TestCaseDto>,
+}
+
+/// Data transfer object for a single test case stored in JSON or YAML file.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TestCaseDto {
+  /// Optional identifier of the test case.
+  #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  /// Optional name of the test case.
+  #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// Type of the test case, omitted for the default type `decision`.
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub typ: Option<String>,
+  /// Optional description of the test case.
+  #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// Optional name of the invoked decision service or business knowledge model.
+  #[serde(rename = "invocableName", skip_serializing_if = "Option::is_none")]
+  pub invocable_name: Option<String>,
+  /// Input values of the test case.
+  #[serde(rename = "inputNodes", default, skip_serializing_if = "Vec::is_empty")]
+  pub input_nodes: Vec<InputNodeDto>,
+  /// Expected results of the test case.
+  #[serde(rename = "resultNodes", default, skip_serializing_if = "Vec::is_empty")]
+  pub result_nodes: Vec<ResultNodeDto>,
+}
+
+/// Data transfer object for a result node stored in JSON or YAML file.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ResultNodeDto {
+  /// Name of the evaluated decision, decision service or business knowledge model.
+  #[serde(rename = "name")]
+  pub name: String,
+  /// Flag indicating if the evaluation is expected to fail.
+  #[serde(rename = "errorResult", default, skip_serializing_if = "is_false")]
+  pub error_result: bool,
+  /// Type of the result node, omitted for the default type `decision`.
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub typ: Option<String>,
+  /// Optional type the actual value is casted to before comparison.
+  #[serde(rename = "cast", skip_serializing_if = "Option::is_none")]
+  pub cast: Option<String>,
+  /// Optional value computed by the engine.
+  #[serde(rename = "computed", skip_serializing_if = "Option::is_none")]
+  pub computed: Option<ValueDto>,
+  /// Optional expected value.
+  #[serde(rename = "expected", skip_serializing_if = "Option::is_none")]
+  pub expected: Option<ValueDto>,
+}
+
+/// Data transfer object for an input node.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct InputNodeDto {
+  /// Name of the input node.
   #[serde(rename = "name")]
   pub name: String,
+  /// Optional value of the input node.
   #[serde(rename = "value")]
   pub value: Option<ValueDto>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Data transfer object for the expected value.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ExpectedValueDto {
+  /// Optional value, missing value is nil.
   #[serde(rename = "value")]
   pub value: Option<ValueDto>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// Data transfer object for a value, exactly one of simple value, components or list is expected.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ValueDto {
+  /// Simple value.
   #[serde(rename = "simple", skip_serializing_if = "Option::is_none")]
   pub simple: Option<SimpleDto>,
+  /// Components of the context.
   #[serde(rename = "components", skip_serializing_if = "Option::is_none")]
   pub components: Option<Vec<ComponentDto>>,
+  /// List of values.
   #[serde(rename = "list", skip_serializing_if = "Option::is_none")]
   pub list: Option<ListDto>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// Data transfer object for a simple value.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SimpleDto {
+  /// Type of the value in namespace-prefixed form.
   #[serde(rename = "type")]
   pub typ: Option<String>,
+  /// Optional text representing the value.
   #[serde(rename = "text")]
   pub text: Option<String>,
-  #[serde(rename = "isNil")]
+  /// Flag indicating if the value is nil.
+  #[serde(rename = "isNil", default)]
   pub nil: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// Data transfer object for a component of the context.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ComponentDto {
+  /// Optional name of the component.
   #[serde(rename = "name")]
   pub name: Option<String>,
+  /// Optional value of the component.
   #[serde(rename = "value")]
   pub value: Option<ValueDto>,
-  #[serde(rename = "isNil")]
+  /// Flag indicating if the component is nil.
+  #[serde(rename = "isNil", default)]
   pub nil: bool,
 }
 
@@ -68,20 +149,20 @@ impl From<&Component> for ComponentDto {
   fn from(component: &Component) -> Self {
     Self {
       name: component.name.clone(),
-      value: match &component.value {
-        Some(value) => Some(value.into()),
-        _ => None,
-      },
+      value: component.value.as_ref().map(ValueDto::from),
       nil: component.nil,
     }
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// Data transfer object for a list of values.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ListDto {
-  #[serde(rename = "items")]
+  /// Items of the list, may be empty.
+  #[serde(rename = "items", default)]
   pub items: Vec<ValueDto>,
-  #[serde(rename = "isNil")]
+  /// Flag indicating if the list is nil.
+  #[serde(rename = "isNil", default)]
   pub nil: bool,
 }
 
@@ -98,10 +179,7 @@ impl From<&InputNode> for InputNodeDto {
   fn from(input_node: &InputNode) -> Self {
     Self {
       name: input_node.name.clone(),
-      value: match &input_node.value {
-        Some(value_type) => Some(value_type.into()),
-        _ => None,
-      },
+      value: input_node.value.as_ref().map(ValueDto::from),
     }
   }
 }
@@ -116,16 +194,6 @@ impl From<&Simple> for SimpleDto {
   }
 }
 
-impl Default for ValueDto {
-  fn default() -> Self {
-    Self {
-      simple: None,
-      components: None,
-      list: None,
-    }
-  }
-}
-
 impl From<&Value> for ValueDto {
   fn from(value: &Value) -> Self {
     match &value {
@@ -144,3 +212,134 @@ impl From<&Value> for ValueDto {
     }
   }
 }
+
+impl From<&TestCases> for TestCasesDto {
+  fn from(test_cases: &TestCases) -> Self {
+    Self {
+      model_name: test_cases.model_name.clone(),
+      labels: test_cases.labels.clone(),
+      test_cases: test_cases.test_cases.iter().map(TestCaseDto::from).collect(),
+    }
+  }
+}
+
+impl From<&TestCase> for TestCaseDto {
+  fn from(test_case: &TestCase) -> Self {
+    Self {
+      id: test_case.id.clone(),
+      name: test_case.name.clone(),
+      typ: type_name(&test_case.typ),
+      description: test_case.description.clone(),
+      invocable_name: test_case.invocable_name.clone(),
+      input_nodes: test_case.input_nodes.iter().map(InputNodeDto::from).collect(),
+      result_nodes: test_case.result_nodes.iter().map(ResultNodeDto::from).collect(),
+    }
+  }
+}
+
+impl From<&ResultNode> for ResultNodeDto {
+  fn from(result_node: &ResultNode) -> Self {
+    Self {
+      name: result_node.name.clone(),
+      error_result: result_node.error_result,
+      typ: type_name(&result_node.typ),
+      cast: result_node.cast.clone(),
+      computed: result_node.computed.as_ref().map(ValueDto::from),
+      expected: result_node.expected.as_ref().map(ValueDto::from),
+    }
+  }
+}
+
+impl From<&TestCasesDto> for TestCases {
+  fn from(test_cases: &TestCasesDto) -> Self {
+    Self {
+      model_name: test_cases.model_name.clone(),
+      labels: test_cases.labels.clone(),
+      test_cases: test_cases.test_cases.iter().map(TestCase::from).collect(),
+    }
+  }
+}
+
+impl From<&TestCaseDto> for TestCase {
+  fn from(test_case: &TestCaseDto) -> Self {
+    Self {
+      id: test_case.id.clone(),
+      name: test_case.name.clone(),
+      typ: test_case.typ.clone().into(),
+      description: test_case.description.clone(),
+      invocable_name: test_case.invocable_name.clone(),
+      input_nodes: test_case.input_nodes.iter().map(InputNode::from).collect(),
+      result_nodes: test_case.result_nodes.iter().map(ResultNode::from).collect(),
+    }
+  }
+}
+
+impl From<&InputNodeDto> for InputNode {
+  fn from(input_node: &InputNodeDto) -> Self {
+    Self {
+      name: input_node.name.clone(),
+      value: input_node.value.as_ref().map(Value::from),
+    }
+  }
+}
+
+impl From<&ResultNodeDto> for ResultNode {
+  fn from(result_node: &ResultNodeDto) -> Self {
+    Self {
+      name: result_node.name.clone(),
+      error_result: result_node.error_result,
+      typ: result_node.typ.clone().into(),
+      cast: result_node.cast.clone(),
+      expected: result_node.expected.as_ref().map(Value::from),
+      computed: result_node.computed.as_ref().map(Value::from),
+    }
+  }
+}
+
+impl From<&ValueDto> for Value {
+  /// Converts [ValueDto] into [Value], empty [ValueDto] is converted into nil simple value.
+  fn from(value: &ValueDto) -> Self {
+    if let Some(simple) = &value.simple {
+      Value::Simple(Simple {
+        typ: simple.typ.clone(),
+        text: simple.text.clone(),
+        nil: simple.nil,
+      })
+    } else if let Some(components) = &value.components {
+      Value::Components(
+        components
+          .iter()
+          .map(|component| Component {
+            name: component.name.clone(),
+            value: component.value.as_ref().map(Value::from),
+            nil: component.nil,
+          })
+          .collect(),
+      )
+    } else if let Some(list) = &value.list {
+      Value::List(List {
+        items: list.items.iter().map(Value::from).collect(),
+        nil: list.nil,
+      })
+    } else {
+      Value::Simple(Simple {
+        typ: None,
+        text: None,
+        nil: true,
+      })
+    }
+  }
+}
+
+/// Returns the name of the test case type, the default type `decision` is omitted.
+fn type_name(typ: &TestCaseType) -> Option<String> {
+  match typ {
+    TestCaseType::Decision => None,
+    other => Some(other.to_string()),
+  }
+}
+
+/// Returns `true` when the flag is not set, used to skip serializing default values.
+fn is_false(flag: &bool) -> bool {
+  !*flag
+}
@@ -0,0 +1,328 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Encoding of values exchanged with the engine.
+//!
+//! Values are sent either in typed envelopes ([ValueDto]) or as plain JSON,
+//! where numbers and booleans are JSON numbers and booleans, contexts are
+//! JSON objects, lists are JSON arrays, nil values are `null` and all other
+//! values (strings, dates, times, durations) are JSON strings.
+//! Numbers are sent with all digits of their textual representation.
+//! Components of contexts keep their order in both directions.
+//! Plain JSON values carry only the kind of the value (number, boolean or string),
+//! types of decoded values are recovered from expected values of the same kind before comparison.
+
+use crate::compare::{local_name, normalize_decimal, NUMERIC_TYPES};
+use crate::dto::{ActualValueDto, ComponentDto, ListDto, SimpleDto, ValueDto};
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use crate::params::EvaluateParams;
+use crate::results::ResultDto;
+use serde_json::{Map, Number, Value};
+
+/// Type of simple values decoded from JSON numbers.
+pub const TYPE_DECIMAL: &str = "xsd:decimal";
+
+/// Type of simple values decoded from JSON booleans.
+pub const TYPE_BOOLEAN: &str = "xsd:boolean";
+
+/// Type of simple values decoded from JSON strings.
+pub const TYPE_STRING: &str = "xsd:string";
+
+/// Encoding of values exchanged with the engine.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ValueEncoding {
+  /// Values are sent in typed envelopes.
+  #[default]
+  #[serde(rename = "typed")]
+  Typed,
+  /// Values are sent as plain JSON.
+  #[serde(rename = "plain")]
+  Plain,
+}
+
+/// Serializes evaluation parameters, encoding input values and arguments.
+pub fn encode_params(params: &EvaluateParams, encoding: ValueEncoding) -> Result<Value> {
+  let mut value = serde_json::to_value(params).map_err(|reason| SerializingFailed(reason.to_string()))?;
+  if encoding == ValueEncoding::Plain {
+    if let Some(Value::Array(input)) = value.get_mut("input") {
+      for (input_node, input_dto) in input.iter_mut().zip(params.input.iter()) {
+        input_node["value"] = encode_optional(input_dto.value.as_ref())?;
+      }
+    }
+    if let Some(arguments) = &params.arguments {
+      value["arguments"] = Value::Array(
+        arguments
+          .iter()
+          .map(|a| encode_optional(a.as_ref()))
+          .collect::<Result<_>>()?,
+      );
+    }
+  }
+  Ok(value)
+}
+
+/// Deserializes the evaluation result. When values are encoded as plain JSON,
+/// the result is expected to be an object with the plain value in `value` member.
+pub fn decode_result(result: ResultDto<Value>, encoding: ValueEncoding) -> Result<ResultDto<ActualValueDto>> {
+  let data = match result.data {
+    Some(data) => Some(match encoding {
+      ValueEncoding::Typed => serde_json::from_value(data).map_err(|reason| InvalidResponse(reason.to_string()))?,
+      ValueEncoding::Plain => match data {
+        Value::Object(members) => ActualValueDto {
+          value: members.get("value").map(decode_value),
+        },
+        other => return Err(InvalidResponse(format!("expected object with value, found: {}", other))),
+      },
+    }),
+    None => None,
+  };
+  Ok(ResultDto {
+    data,
+    errors: result.errors,
+  })
+}
+
+/// Encodes optional value as plain JSON, missing value is encoded as `null`.
+fn encode_optional(value: Option<&ValueDto>) -> Result<Value> {
+  value.map_or(Ok(Value::Null), encode_value)
+}
+
+/// Encodes value as plain JSON, components of contexts keep their order.
+/// Contexts with more components having the same name can not be encoded as JSON objects.
+pub fn encode_value(value: &ValueDto) -> Result<Value> {
+  Ok(if let Some(simple) = &value.simple {
+    encode_simple(simple)
+  } else if let Some(components) = &value.components {
+    let mut members = Map::new();
+    for component in components {
+      let name = component.name.clone().unwrap_or_default();
+      let value = if component.nil {
+        Value::Null
+      } else {
+        encode_optional(component.value.as_ref())?
+      };
+      if members.insert(name.clone(), value).is_some() {
+        return Err(SerializingFailed(format!(
+          "context with duplicated component '{}' can not be encoded as plain JSON",
+          name
+        )));
+      }
+    }
+    Value::Object(members)
+  } else if let Some(list) = &value.list {
+    if list.nil {
+      Value::Null
+    } else {
+      Value::Array(list.items.iter().map(encode_value).collect::<Result<_>>()?)
+    }
+  } else {
+    Value::Null
+  })
+}
+
+/// Encodes simple value, numbers and booleans are encoded as JSON numbers and booleans.
+fn encode_simple(simple: &SimpleDto) -> Value {
+  let text = match &simple.text {
+    Some(text) if !simple.nil => text,
+    _ => return Value::Null,
+  };
+  let typ = simple.typ.as_deref().map(local_name).unwrap_or_default();
+  if NUMERIC_TYPES.contains(&typ) {
+    if let Some(number) = json_number(text.trim()) {
+      return Value::Number(number);
+    }
+  } else if typ == "boolean" {
+    match text.trim() {
+      "true" => return Value::Bool(true),
+      "false" => return Value::Bool(false),
+      _ => {}
+    }
+  }
+  Value::String(text.clone())
+}
+
+/// Converts the textual representation of the number into JSON number with the same digits,
+/// numbers not allowed in JSON (like `.5` or `+1`) are normalized first.
+fn json_number(text: &str) -> Option<Number> {
+  serde_json::from_str::<Number>(text)
+    .ok()
+    .or_else(|| serde_json::from_str::<Number>(&normalize_decimal(text)?).ok())
+}
+
+/// Decodes value from plain JSON. Decoded simple values get the type
+/// corresponding to the kind of JSON value: numbers get [TYPE_DECIMAL], booleans
+/// get [TYPE_BOOLEAN] and strings get [TYPE_STRING], `null` has no type.
+pub fn decode_value(value: &Value) -> ValueDto {
+  let simple = |typ: &str, text: Option<String>| ValueDto {
+    simple: Some(SimpleDto {
+      typ: text.as_ref().map(|_| typ.to_string()),
+      nil: text.is_none(),
+      text,
+    }),
+    ..Default::default()
+  };
+  match value {
+    Value::Null => simple("", None),
+    Value::Bool(b) => simple(TYPE_BOOLEAN, Some(b.to_string())),
+    Value::Number(n) => simple(TYPE_DECIMAL, Some(n.to_string())),
+    Value::String(s) => simple(TYPE_STRING, Some(s.clone())),
+    Value::Array(items) => ValueDto {
+      list: Some(ListDto {
+        items: items.iter().map(decode_value).collect(),
+        nil: false,
+      }),
+      ..Default::default()
+    },
+    Value::Object(members) => ValueDto {
+      components: Some(
+        members
+          .iter()
+          .map(|(name, value)| ComponentDto {
+            name: Some(name.clone()),
+            value: Some(decode_value(value)),
+            nil: false,
+          })
+          .collect(),
+      ),
+      ..Default::default()
+    },
+  }
+}
+
+/// Recovers type information of the actual value from the expected value.
+///
+/// Simple values get the type of the corresponding expected value of the same kind,
+/// numbers equal to expected numbers get the textual representation of the expected number,
+/// `null` gets the structure of the expected nil list or nil component.
+pub fn recover_types(actual: &ValueDto, expected: &ValueDto) -> ValueDto {
+  match (&actual.simple, &actual.components, &actual.list) {
+    (Some(a), _, _) => match (&expected.simple, &expected.list) {
+      (Some(e), _) => ValueDto {
+        simple: Some(recover_simple(a, e)),
+        ..Default::default()
+      },
+      (None, Some(list)) if a.nil && list.nil => ValueDto {
+        list: Some(ListDto {
+          items: vec![],
+          nil: true,
+        }),
+        ..Default::default()
+      },
+      _ => actual.clone(),
+    },
+    (None, Some(components), _) => match &expected.components {
+      Some(expected_components) => ValueDto {
+        components: Some(
+          components
+            .iter()
+            .map(|component| {
+              let expected_component = expected_components.iter().find(|e| e.name == component.name);
+              if expected_component.is_some_and(|e| e.nil) && is_null(component.value.as_ref()) {
+                return ComponentDto {
+                  name: component.name.clone(),
+                  value: None,
+                  nil: true,
+                };
+              }
+              let expected_value = expected_component.and_then(|e| e.value.as_ref());
+              ComponentDto {
+                name: component.name.clone(),
+                value: match (&component.value, expected_value) {
+                  (Some(a), Some(e)) => Some(recover_types(a, e)),
+                  (value, _) => value.clone(),
+                },
+                nil: component.nil,
+              }
+            })
+            .collect(),
+        ),
+        ..Default::default()
+      },
+      None => actual.clone(),
+    },
+    (None, None, Some(list)) => match &expected.list {
+      Some(expected_list) => ValueDto {
+        list: Some(ListDto {
+          items: list
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| match expected_list.items.get(index) {
+              Some(e) => recover_types(item, e),
+              None => item.clone(),
+            })
+            .collect(),
+          nil: list.nil,
+        }),
+        ..Default::default()
+      },
+      None => actual.clone(),
+    },
+    _ => actual.clone(),
+  }
+}
+
+/// Returns `true` when the value was decoded from `null`.
+fn is_null(value: Option<&ValueDto>) -> bool {
+  value
+    .and_then(|value| value.simple.as_ref())
+    .is_some_and(|simple| simple.nil && simple.typ.is_none())
+}
+
+/// Recovers the type of simple value, only when the kind of the decoded value
+/// (number, boolean or string) matches the type of the expected value.
+/// Values not matching the expected type keep the type of their kind.
+fn recover_simple(actual: &SimpleDto, expected: &SimpleDto) -> SimpleDto {
+  let expected_typ = expected.typ.as_deref().map(local_name);
+  let numeric = expected_typ.is_some_and(|t| NUMERIC_TYPES.contains(&t));
+  let matching = match (actual.typ.as_deref(), expected_typ) {
+    (None, _) | (_, None) => true,
+    (Some(TYPE_DECIMAL), _) => numeric,
+    (Some(TYPE_BOOLEAN), Some(t)) => t == "boolean",
+    (Some(TYPE_STRING), Some(t)) => !numeric && t != "boolean",
+    _ => false,
+  };
+  if !matching {
+    return actual.clone();
+  }
+  let text = match (&actual.text, &expected.text) {
+    (Some(a), Some(e)) if numeric && same_number(a, e) => Some(e.clone()),
+    (text, _) => text.clone(),
+  };
+  SimpleDto {
+    typ: expected.typ.clone(),
+    text,
+    nil: actual.nil,
+  }
+}
+
+/// Returns `true` when both texts represent the same number.
+fn same_number(a: &str, b: &str) -> bool {
+  let normalize = |text: &str| {
+    normalize_decimal(text.trim()).or_else(|| {
+      text
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|n| normalize_decimal(&n.to_string()))
+    })
+  };
+  match (normalize(a), normalize(b)) {
+    (Some(a), Some(b)) => a == b,
+    _ => false,
+  }
+}
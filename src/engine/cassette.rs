@@ -0,0 +1,321 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Recording and replaying of the traffic exchanged with the engine.
+//!
+//! Every request is stored together with the response (or the transport error)
+//! in a separate file in the cassette directory, including the raw traffic
+//! (like HTTP request line, headers and body) when provided by the engine.
+//! Evaluations are recorded in files named after the test file, test identifier and result node,
+//! other operations in files named after the model tag. Repeated requests with the same name
+//! are recorded in separate files numbered in the order of requests, and replayed in the same order.
+//! Replayed requests are compared with recorded requests, the `source` of deployed models
+//! contains the absolute path of the model file, so it is excluded from the comparison.
+
+use crate::dto::ActualValueDto;
+use crate::engine::{Engine, Exchange};
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use crate::params::{DeployParams, EvaluateParams, UndeployParams, VerifyParams};
+use crate::results::{DeployResult, ResultDto, UndeployResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Single request recorded together with its response.
+#[derive(Debug, Serialize, Deserialize)]
+struct Recording {
+  /// Name of the operation.
+  #[serde(rename = "operation")]
+  operation: String,
+  /// Request parameters.
+  #[serde(rename = "request")]
+  request: Value,
+  /// Raw request sent to the engine.
+  #[serde(rename = "rawRequest", default, skip_serializing_if = "Option::is_none")]
+  raw_request: Option<String>,
+  /// Raw response received from the engine.
+  #[serde(rename = "rawResponse", default, skip_serializing_if = "Option::is_none")]
+  raw_response: Option<String>,
+  /// Response returned by the engine.
+  #[serde(rename = "response", skip_serializing_if = "Option::is_none")]
+  response: Option<Value>,
+  /// Transport error, when no valid response was received.
+  #[serde(rename = "transportError", skip_serializing_if = "Option::is_none")]
+  transport_error: Option<String>,
+  /// Invalid response error, when the response could not be parsed.
+  #[serde(rename = "invalidResponse", skip_serializing_if = "Option::is_none")]
+  invalid_response: Option<String>,
+}
+
+/// Directory containing recorded requests and responses.
+struct Cassette {
+  /// Directory of recording files.
+  dir: PathBuf,
+  /// Test file, test identifier and result node of the evaluated test.
+  test: (String, String, String),
+  /// Number of requests recorded or replayed so far, indexed by the name of the recording.
+  counters: HashMap<String, usize>,
+}
+
+impl Cassette {
+  /// Creates the cassette in specified directory.
+  fn new(dir: &str) -> Self {
+    Self {
+      dir: PathBuf::from(dir),
+      test: Default::default(),
+      counters: HashMap::new(),
+    }
+  }
+
+  /// Returns the name of the recording of the request, evaluations are named after the evaluated test,
+  /// other operations are named after the tag of the model.
+  fn name(&self, operation: &str, request: &Value) -> String {
+    let parts = if operation == "evaluate" {
+      let (file_name, test_id, result_name) = &self.test;
+      vec![operation, file_name, test_id, result_name]
+    } else {
+      vec![operation, request["tag"].as_str().unwrap_or_default()]
+    };
+    parts
+      .iter()
+      .map(|part| sanitize(part))
+      .collect::<Vec<String>>()
+      .join("-")
+  }
+
+  /// Returns the path of the file for the next request with specified name, the counter of requests is advanced.
+  fn next_file_path(&mut self, name: &str) -> PathBuf {
+    let counter = self.counters.entry(name.to_string()).or_default();
+    let file_path = self.dir.join(format!("{}-{}.json", name, counter));
+    *counter += 1;
+    file_path
+  }
+
+  /// Records the request, the raw traffic and the result returned by the engine.
+  fn record<P: Serialize, R: Serialize>(
+    &mut self,
+    operation: &str,
+    params: &P,
+    exchange: Option<Exchange>,
+    result: &Result<R>,
+  ) -> Result<()> {
+    let request = to_value(params)?;
+    let (raw_request, raw_response) = match exchange {
+      Some(exchange) => (Some(exchange.request), Some(exchange.response)),
+      None => (None, None),
+    };
+    let mut recording = Recording {
+      operation: operation.to_string(),
+      request,
+      raw_request,
+      raw_response,
+      response: None,
+      transport_error: None,
+      invalid_response: None,
+    };
+    match result {
+      Ok(response) => recording.response = Some(to_value(response)?),
+      Err(TransportFailed(reason)) => recording.transport_error = Some(reason.clone()),
+      Err(InvalidResponse(reason)) => recording.invalid_response = Some(reason.clone()),
+      Err(_) => return Ok(()),
+    }
+    let content =
+      serde_json::to_string_pretty(&recording).map_err(|reason| SerializingFailed(format!("{}", reason)))?;
+    let name = self.name(operation, &recording.request);
+    fs::create_dir_all(&self.dir)?;
+    fs::write(self.next_file_path(&name), content)?;
+    Ok(())
+  }
+
+  /// Returns `true` when the next request with the same name was recorded.
+  fn contains<P: Serialize>(&self, operation: &str, params: &P) -> Result<bool> {
+    let name = self.name(operation, &to_value(params)?);
+    let counter = self.counters.get(&name).copied().unwrap_or_default();
+    Ok(self.dir.join(format!("{}-{}.json", name, counter)).exists())
+  }
+
+  /// Replays the result recorded for the request, the request must be the same as the recorded one.
+  fn replay<P: Serialize, R: DeserializeOwned>(&mut self, operation: &str, params: &P) -> Result<R> {
+    let request = to_value(params)?;
+    let name = self.name(operation, &request);
+    let file_path = self.next_file_path(&name);
+    let content = fs::read_to_string(&file_path).map_err(|_| {
+      TransportFailed(format!(
+        "no recorded response for {} request in '{}'",
+        operation,
+        file_path.display()
+      ))
+    })?;
+    let recording: Recording = serde_json::from_str(&content)
+      .map_err(|reason| ParsingJSONFailed(format!("{}: {}", file_path.display(), reason)))?;
+    if without_source(&recording.request) != without_source(&request) {
+      return Err(ReplayMismatch(format!(
+        "{} request differs from the request recorded in '{}'",
+        operation,
+        file_path.display()
+      )));
+    }
+    match recording {
+      Recording {
+        response: Some(response),
+        ..
+      } => serde_json::from_value(response).map_err(|reason| InvalidResponse(reason.to_string())),
+      Recording {
+        transport_error: Some(reason),
+        ..
+      } => Err(TransportFailed(reason)),
+      Recording {
+        invalid_response: Some(reason),
+        ..
+      } => Err(InvalidResponse(reason)),
+      _ => Err(InvalidResponse(format!("empty recording in '{}'", file_path.display()))),
+    }
+  }
+}
+
+/// Returns the request without the `source` of the deployed model.
+fn without_source(request: &Value) -> Value {
+  let mut request = request.clone();
+  if let Value::Object(members) = &mut request {
+    members.remove("source");
+  }
+  request
+}
+
+/// Replaces characters not allowed in file names with underscores.
+fn sanitize(text: &str) -> String {
+  text
+    .chars()
+    .map(|ch| {
+      if ch.is_alphanumeric() || ch == '.' || ch == '_' {
+        ch
+      } else {
+        '_'
+      }
+    })
+    .collect()
+}
+
+/// Serializes request or response.
+fn to_value<T: Serialize>(value: &T) -> Result<Value> {
+  serde_json::to_value(value).map_err(|reason| SerializingFailed(format!("{}", reason)))
+}
+
+/// Engine recording all traffic exchanged with the wrapped engine.
+pub struct RecordingEngine {
+  engine: Box<dyn Engine>,
+  cassette: Cassette,
+}
+
+impl RecordingEngine {
+  /// Creates engine recording traffic of the wrapped engine in specified directory.
+  pub fn new(engine: Box<dyn Engine>, dir: &str) -> Self {
+    Self {
+      engine,
+      cassette: Cassette::new(dir),
+    }
+  }
+}
+
+impl Engine for RecordingEngine {
+  fn deploy(&mut self, params: &DeployParams) -> Result<ResultDto<DeployResult>> {
+    let result = self.engine.deploy(params);
+    let exchange = self.engine.take_exchange();
+    self.cassette.record("deploy", params, exchange, &result)?;
+    result
+  }
+
+  fn evaluate(&mut self, params: &EvaluateParams) -> Result<ResultDto<ActualValueDto>> {
+    let result = self.engine.evaluate(params);
+    let exchange = self.engine.take_exchange();
+    self.cassette.record("evaluate", params, exchange, &result)?;
+    result
+  }
+
+  fn undeploy(&mut self, params: &UndeployParams) -> Result<ResultDto<UndeployResult>> {
+    let result = self.engine.undeploy(params);
+    let exchange = self.engine.take_exchange();
+    self.cassette.record("undeploy", params, exchange, &result)?;
+    result
+  }
+
+  fn verify(&mut self, params: &VerifyParams) -> Result<bool> {
+    let result = self.engine.verify(params);
+    let exchange = self.engine.take_exchange();
+    self.cassette.record("verify", params, exchange, &result)?;
+    result
+  }
+
+  fn health(&mut self) -> Result<()> {
+    self.engine.health()
+  }
+
+  fn set_test(&mut self, file_name: &str, test_id: &str, result_name: &str) {
+    self.cassette.test = (file_name.to_string(), test_id.to_string(), result_name.to_string());
+    self.engine.set_test(file_name, test_id, result_name);
+  }
+}
+
+/// Engine replaying the traffic recorded earlier, no engine is running during replay.
+pub struct ReplayEngine {
+  cassette: Cassette,
+}
+
+impl ReplayEngine {
+  /// Creates engine replaying traffic recorded in specified directory.
+  pub fn new(dir: &str) -> Self {
+    Self {
+      cassette: Cassette::new(dir),
+    }
+  }
+}
+
+impl Engine for ReplayEngine {
+  fn deploy(&mut self, params: &DeployParams) -> Result<ResultDto<DeployResult>> {
+    self.cassette.replay("deploy", params)
+  }
+
+  fn evaluate(&mut self, params: &EvaluateParams) -> Result<ResultDto<ActualValueDto>> {
+    self.cassette.replay("evaluate", params)
+  }
+
+  /// Undeploying is not recorded when the engine does not support it,
+  /// so missing recordings are reported as not supported.
+  fn undeploy(&mut self, params: &UndeployParams) -> Result<ResultDto<UndeployResult>> {
+    if self.cassette.contains("undeploy", params)? {
+      self.cassette.replay("undeploy", params)
+    } else {
+      Err(NotSupported("undeploying models".to_string()))
+    }
+  }
+
+  fn verify(&mut self, params: &VerifyParams) -> Result<bool> {
+    self.cassette.replay("verify", params)
+  }
+
+  /// Recorded traffic is always available.
+  fn health(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  fn set_test(&mut self, file_name: &str, test_id: &str, result_name: &str) {
+    self.cassette.test = (file_name.to_string(), test_id.to_string(), result_name.to_string());
+  }
+}
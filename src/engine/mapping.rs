@@ -0,0 +1,143 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Mapping of requests and responses for engines with custom REST API.
+//!
+//! Requests are built from JSON templates containing placeholders `${/json/pointer}`,
+//! pointing values in default request parameters. A string consisting of a single
+//! placeholder is replaced with the pointed value of any type, placeholders embedded
+//! in longer strings are replaced with the textual representation of the pointed value,
+//! placeholders in URLs are replaced with the percent-encoded textual representation.
+//! Results and errors are extracted from responses using JSON pointers.
+
+use crate::config::OperationMapping;
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use crate::results::{ErrorDto, ResultDto};
+use regex::{Captures, Regex};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+lazy_static! {
+  /// Pattern of the placeholder.
+  static ref PLACEHOLDER: Regex = Regex::new(r"\$\{([^}]*)\}").unwrap();
+}
+
+/// Value returned when the pointed value does not exist.
+static NULL: Value = Value::Null;
+
+/// Default JSON pointer to the result in the response.
+const DEFAULT_RESULT_POINTER: &str = "/data";
+
+/// Default JSON pointer to errors in the response.
+const DEFAULT_ERRORS_POINTER: &str = "/errors";
+
+/// Renders the template, replacing placeholders with values from context.
+pub fn render(template: &Value, context: &Value) -> Value {
+  match template {
+    Value::String(text) => match PLACEHOLDER.captures(text) {
+      Some(captures) if captures[0].len() == text.len() => lookup(context, &captures[1]).clone(),
+      _ => Value::String(render_text(text, context)),
+    },
+    Value::Array(items) => Value::Array(items.iter().map(|item| render(item, context)).collect()),
+    Value::Object(members) => Value::Object(
+      members
+        .iter()
+        .map(|(name, value)| (render_text(name, context), render(value, context)))
+        .collect(),
+    ),
+    other => other.clone(),
+  }
+}
+
+/// Replaces placeholders in text with textual representation of values from context.
+pub fn render_text(text: &str, context: &Value) -> String {
+  PLACEHOLDER
+    .replace_all(text, |captures: &Captures| to_text(lookup(context, &captures[1])))
+    .to_string()
+}
+
+/// Replaces placeholders in URL with percent-encoded textual representation of values from context,
+/// so substituted values containing reserved characters (like `/`, `?` or spaces) remain single URL segments.
+pub fn render_url(url: &str, context: &Value) -> String {
+  PLACEHOLDER
+    .replace_all(url, |captures: &Captures| {
+      percent_encode(&to_text(lookup(context, &captures[1])))
+    })
+    .to_string()
+}
+
+/// Returns the textual representation of the value, `null` is represented as an empty text.
+fn to_text(value: &Value) -> String {
+  match value {
+    Value::String(value) => value.clone(),
+    Value::Null => "".to_string(),
+    value => value.to_string(),
+  }
+}
+
+/// Encodes all bytes of the text except unreserved characters, as defined in RFC 3986.
+fn percent_encode(text: &str) -> String {
+  text
+    .bytes()
+    .map(|byte| match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+      _ => format!("%{:02X}", byte),
+    })
+    .collect()
+}
+
+/// Extracts the result and errors from the response.
+pub fn extract<T: DeserializeOwned>(response: &Value, mapping: Option<&OperationMapping>) -> Result<ResultDto<T>> {
+  let result_pointer = mapping
+    .and_then(|m| m.result_pointer.as_deref())
+    .unwrap_or(DEFAULT_RESULT_POINTER);
+  let errors_pointer = mapping
+    .and_then(|m| m.errors_pointer.as_deref())
+    .unwrap_or(DEFAULT_ERRORS_POINTER);
+  let data = match lookup(response, result_pointer) {
+    Value::Null => None,
+    value => Some(
+      serde_json::from_value(value.clone())
+        .map_err(|reason| InvalidResponse(format!("invalid result at '{}': {}", result_pointer, reason)))?,
+    ),
+  };
+  let errors = match lookup(response, errors_pointer) {
+    Value::Null => None,
+    Value::Array(items) => Some(items.iter().map(error_dto).collect()),
+    value => Some(vec![error_dto(value)]),
+  };
+  Ok(ResultDto { data, errors })
+}
+
+/// Converts the error reported by the engine into error object,
+/// error details are taken from `details` or `message` member when present.
+fn error_dto(value: &Value) -> ErrorDto {
+  let details = match value {
+    Value::String(text) => text.clone(),
+    Value::Object(members) => match members.get("details").or_else(|| members.get("message")) {
+      Some(Value::String(text)) => text.clone(),
+      _ => value.to_string(),
+    },
+    _ => value.to_string(),
+  };
+  ErrorDto { details }
+}
+
+/// Returns the value pointed by JSON pointer, or `null` when there is no such value.
+fn lookup<'a>(value: &'a Value, pointer: &str) -> &'a Value {
+  value.pointer(pointer.trim()).unwrap_or(&NULL)
+}
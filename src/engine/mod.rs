@@ -0,0 +1,104 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Adapters for DMN™ engines tested by the runner.
+//!
+//! The runner communicates with the tested engine only through the [Engine] trait,
+//! so engines available via different transports (or linked in-process) can be
+//! tested without changing the runner.
+
+use crate::config::ConfigurationParams;
+use crate::dto::ActualValueDto;
+use crate::errors::Result;
+use crate::errors::RunnerError::InvalidConfiguration;
+use crate::params::{DeployParams, EvaluateParams, UndeployParams, VerifyParams};
+use crate::results::{DeployResult, ResultDto, UndeployResult};
+use std::time::Duration;
+
+pub mod cassette;
+pub mod mapping;
+pub mod process;
+pub mod rest;
+
+/// Operations the runner requires from the tested DMN™ engine.
+///
+/// Transport-level problems (engine not reachable, invalid response) are reported as
+/// [RunnerError::TransportFailed](crate::errors::RunnerError::TransportFailed)
+/// or [RunnerError::InvalidResponse](crate::errors::RunnerError::InvalidResponse),
+/// errors reported by the engine itself are returned in [ResultDto].
+/// Engines may be used from other threads, e.g. when evaluations are sent concurrently.
+pub trait Engine: Send {
+  /// Deploys the model.
+  fn deploy(&mut self, params: &DeployParams) -> Result<ResultDto<DeployResult>>;
+  /// Evaluates the decision artifact.
+  fn evaluate(&mut self, params: &EvaluateParams) -> Result<ResultDto<ActualValueDto>>;
+  /// Removes the deployed model.
+  fn undeploy(&mut self, params: &UndeployParams) -> Result<ResultDto<UndeployResult>>;
+  /// Checks if the model deployed earlier is still available,
+  /// engines not supporting verification should return `Ok(true)`.
+  fn verify(&mut self, params: &VerifyParams) -> Result<bool>;
+  /// Checks if the engine is up and ready to process requests.
+  fn health(&mut self) -> Result<()>;
+  /// Informs the engine about the result node of the test case evaluated next, used by engines
+  /// recording the traffic. The name of the test file is relative to the test cases directory.
+  fn set_test(&mut self, _file_name: &str, _test_id: &str, _result_name: &str) {}
+  /// Returns the raw traffic of the last request sent to the engine, when available.
+  fn take_exchange(&mut self) -> Option<Exchange> {
+    None
+  }
+}
+
+/// Raw traffic of a single request sent to the engine.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Exchange {
+  /// Raw request, like the HTTP request line, headers and body.
+  pub request: String,
+  /// Raw response, like the HTTP status line, headers and body, empty when no response was received.
+  pub response: String,
+}
+
+/// Default timeout of a single request sent to the engine process, in milliseconds.
+const DEFAULT_ENGINE_TIMEOUT_MS: u64 = 30_000;
+
+/// Creates the engine adapter specified in configuration.
+/// When the engine command is configured, the engine is run as a subprocess,
+/// otherwise the engine is accessed via REST endpoints.
+/// When the replay directory is configured, no engine is created and recorded traffic
+/// is replayed, when the record directory is configured, the engine traffic is recorded.
+pub fn create(config: &ConfigurationParams) -> Result<Box<dyn Engine>> {
+  if let Some(replay_dir_path) = &config.replay_dir_path {
+    if config.record_dir_path.is_some() {
+      return Err(InvalidConfiguration(
+        "recording and replaying can not be used together".to_string(),
+      ));
+    }
+    return Ok(Box::new(cassette::ReplayEngine::new(replay_dir_path)));
+  }
+  let engine: Box<dyn Engine> = if let Some(command) = &config.engine_command {
+    let timeout = Duration::from_millis(config.engine_timeout_ms.unwrap_or(DEFAULT_ENGINE_TIMEOUT_MS));
+    Box::new(process::ProcessEngine::new(
+      command.clone(),
+      timeout,
+      config.value_encoding,
+    ))
+  } else {
+    Box::new(rest::RestEngine::new(config)?)
+  };
+  match &config.record_dir_path {
+    Some(record_dir_path) => Ok(Box::new(cassette::RecordingEngine::new(engine, record_dir_path))),
+    None => Ok(engine),
+  }
+}
@@ -0,0 +1,244 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Adapter for engines running as a subprocess.
+//!
+//! The engine process is spawned with the configured command and receives requests
+//! on its standard input, one JSON object per line:
+//! ```json
+//! {"operation":"evaluate","params":{"tag":"...","artifact":"decision","name":"...","input":[...]}}
+//! ```
+//! where `operation` is one of `deploy`, `evaluate`, `undeploy`, `verify` or `health`
+//! and `params` have the same shape as the body of the corresponding REST request.
+//! For every request the engine writes exactly one line with the JSON result
+//! to its standard output, in the same `data`/`errors` envelope as returned by REST endpoints.
+//!
+//! When the process crashes or does not respond within the timeout, it is killed
+//! and restarted before the next request, and all models deployed so far are deployed again.
+
+use crate::dto::ActualValueDto;
+use crate::encoding::{decode_result, encode_params, ValueEncoding};
+use crate::engine::{Engine, Exchange};
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use crate::params::{DeployParams, EvaluateParams, UndeployParams, VerifyParams};
+use crate::results::{DeployResult, ResultDto, UndeployResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const OPERATION_DEPLOY: &str = "deploy";
+const OPERATION_EVALUATE: &str = "evaluate";
+const OPERATION_UNDEPLOY: &str = "undeploy";
+const OPERATION_VERIFY: &str = "verify";
+const OPERATION_HEALTH: &str = "health";
+
+/// Request sent to the engine process.
+#[derive(Serialize)]
+struct Request<'a, P: Serialize> {
+  #[serde(rename = "operation")]
+  operation: &'a str,
+  #[serde(rename = "params", skip_serializing_if = "Option::is_none")]
+  params: Option<&'a P>,
+}
+
+/// Running engine process.
+struct RunningProcess {
+  /// Handle of the child process.
+  child: Child,
+  /// Standard input of the child process.
+  stdin: ChildStdin,
+  /// Lines read from the standard output of the child process by the reader thread.
+  lines: Receiver<std::io::Result<String>>,
+}
+
+impl Drop for RunningProcess {
+  /// Kills the process, when it is still running.
+  fn drop(&mut self) {
+    let _ = self.child.kill();
+    let _ = self.child.wait();
+  }
+}
+
+/// Engine running as a subprocess, communicating with newline-delimited JSON messages.
+pub struct ProcessEngine {
+  /// Command starting the engine process, followed by its arguments.
+  command: Vec<String>,
+  /// Maximum time to wait for a response to a single request.
+  timeout: Duration,
+  /// Currently running process, `None` before the first request or after a crash.
+  process: Option<RunningProcess>,
+  /// Deployment requests to be repeated after the process is restarted, indexed by tag.
+  deployments: Vec<(Option<String>, String)>,
+  /// Encoding of values exchanged with the engine.
+  encoding: ValueEncoding,
+  /// Lines exchanged with the process for the last request.
+  last_exchange: Option<Exchange>,
+}
+
+impl ProcessEngine {
+  /// Creates subprocess engine adapter, the process is started with the first request.
+  pub fn new(command: Vec<String>, timeout: Duration, encoding: ValueEncoding) -> Self {
+    Self {
+      command,
+      timeout,
+      process: None,
+      deployments: vec![],
+      encoding,
+      last_exchange: None,
+    }
+  }
+
+  /// Starts the engine process and repeats all deployments done so far.
+  fn start(&mut self) -> Result<()> {
+    let (program, args) = self
+      .command
+      .split_first()
+      .ok_or_else(|| TransportFailed("engine command is empty".to_string()))?;
+    let mut child = Command::new(program)
+      .args(args)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::inherit())
+      .spawn()
+      .map_err(|reason| TransportFailed(format!("starting engine process failed: {}", reason)))?;
+    let stdin = child
+      .stdin
+      .take()
+      .expect("standard input of engine process is not piped");
+    let stdout = child
+      .stdout
+      .take()
+      .expect("standard output of engine process is not piped");
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+      for line in BufReader::new(stdout).lines() {
+        if sender.send(line).is_err() {
+          break;
+        }
+      }
+    });
+    self.process = Some(RunningProcess {
+      child,
+      stdin,
+      lines: receiver,
+    });
+    let deployments = self.deployments.clone();
+    for (_, request) in &deployments {
+      self.exchange(request)?;
+    }
+    Ok(())
+  }
+
+  /// Sends the request to the engine process and parses the response.
+  fn request<P: Serialize, R: DeserializeOwned>(&mut self, operation: &str, params: Option<&P>) -> Result<R> {
+    let request = serde_json::to_string(&Request { operation, params })
+      .map_err(|reason| SerializingFailed(format!("{}", reason)))?;
+    if self.process.is_none() {
+      self.start()?;
+    }
+    let response = self.exchange(&request);
+    self.last_exchange = Some(Exchange {
+      request: format!("{}\n", request),
+      response: response.as_ref().map(|line| format!("{}\n", line)).unwrap_or_default(),
+    });
+    let response = response?;
+    serde_json::from_str(&response).map_err(|reason| InvalidResponse(format!("{}: {}", reason, response)))
+  }
+
+  /// Writes a single request line and reads a single response line,
+  /// the process is dropped (killed) when the exchange fails.
+  fn exchange(&mut self, request: &str) -> Result<String> {
+    let process = match self.process.as_mut() {
+      Some(process) => process,
+      None => return Err(TransportFailed("engine process is not running".to_string())),
+    };
+    let result = match writeln!(process.stdin, "{}", request).and_then(|_| process.stdin.flush()) {
+      Ok(()) => match process.lines.recv_timeout(self.timeout) {
+        Ok(Ok(line)) => Ok(line),
+        Ok(Err(reason)) => Err(TransportFailed(format!(
+          "reading from engine process failed: {}",
+          reason
+        ))),
+        Err(RecvTimeoutError::Timeout) => Err(TransportFailed(format!(
+          "engine process did not respond within {} ms",
+          self.timeout.as_millis()
+        ))),
+        Err(RecvTimeoutError::Disconnected) => Err(TransportFailed("engine process terminated".to_string())),
+      },
+      Err(reason) => Err(TransportFailed(format!("writing to engine process failed: {}", reason))),
+    };
+    if result.is_err() {
+      self.process = None;
+    }
+    result
+  }
+}
+
+impl Engine for ProcessEngine {
+  fn deploy(&mut self, params: &DeployParams) -> Result<ResultDto<DeployResult>> {
+    let result: ResultDto<DeployResult> = self.request(OPERATION_DEPLOY, Some(params))?;
+    if result.data.is_some() {
+      let request = serde_json::to_string(&Request {
+        operation: OPERATION_DEPLOY,
+        params: Some(params),
+      })
+      .map_err(|reason| SerializingFailed(format!("{}", reason)))?;
+      self.deployments.retain(|(tag, _)| *tag != params.tag);
+      self.deployments.push((params.tag.clone(), request));
+    }
+    Ok(result)
+  }
+
+  fn evaluate(&mut self, params: &EvaluateParams) -> Result<ResultDto<ActualValueDto>> {
+    let params = encode_params(params, self.encoding)?;
+    decode_result(self.request(OPERATION_EVALUATE, Some(&params))?, self.encoding)
+  }
+
+  fn undeploy(&mut self, params: &UndeployParams) -> Result<ResultDto<UndeployResult>> {
+    self.deployments.retain(|(tag, _)| *tag != params.tag);
+    self.request(OPERATION_UNDEPLOY, Some(params))
+  }
+
+  fn verify(&mut self, params: &VerifyParams) -> Result<bool> {
+    Ok(
+      self
+        .request::<_, ResultDto<DeployResult>>(OPERATION_VERIFY, Some(params))?
+        .data
+        .is_some(),
+    )
+  }
+
+  fn health(&mut self) -> Result<()> {
+    let result: ResultDto<serde_json::Value> = self.request::<(), _>(OPERATION_HEALTH, None)?;
+    if result.errors.is_some() {
+      Err(TransportFailed(format!(
+        "engine is not healthy: {}",
+        result.errors_as_string()
+      )))
+    } else {
+      Ok(())
+    }
+  }
+
+  fn take_exchange(&mut self) -> Option<Exchange> {
+    self.last_exchange.take()
+  }
+}
@@ -0,0 +1,315 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Adapter for engines exposing REST endpoints.
+
+use crate::config::{ConfigurationParams, HttpParams, MappingParams, OperationMapping};
+use crate::dto::ActualValueDto;
+use crate::encoding::{decode_result, encode_params, ValueEncoding};
+use crate::engine::mapping::{extract, render, render_url};
+use crate::engine::{Engine, Exchange};
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use crate::params::{DeployParams, EvaluateParams, UndeployParams, VerifyParams};
+use crate::results::{DeployResult, ResultDto, UndeployResult};
+use reqwest::blocking::{Client, Request, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::{Certificate, Identity, Method};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::fs;
+use std::time::Duration;
+
+/// Engine available via REST endpoints accepting and returning JSON.
+pub struct RestEngine {
+  /// HTTP client used for all requests.
+  client: Client,
+  /// URL where models are deployed.
+  deploy_url: String,
+  /// URL where decision artifacts are evaluated.
+  evaluate_url: String,
+  /// Optional URL where deployed models are removed.
+  undeploy_url: Option<String>,
+  /// Optional URL where deployed models are verified.
+  verify_url: Option<String>,
+  /// Optional URL where the engine health is checked.
+  health_url: Option<String>,
+  /// Mapping of requests and responses for engines with custom REST API.
+  mapping: MappingParams,
+  /// Encoding of values exchanged with the engine.
+  encoding: ValueEncoding,
+  /// Headers sent with every request, included in the raw traffic.
+  headers: HeaderMap,
+  /// Raw traffic of the last request.
+  exchange: RefCell<Option<Exchange>>,
+}
+
+impl RestEngine {
+  /// Creates REST engine adapter with endpoints and HTTP parameters specified in configuration.
+  pub fn new(config: &ConfigurationParams) -> Result<Self> {
+    Ok(Self {
+      client: build_client(&config.http)?,
+      deploy_url: config.deploy_url.clone(),
+      evaluate_url: config.evaluate_url.clone(),
+      undeploy_url: config.undeploy_url.clone(),
+      verify_url: config.verify_url.clone(),
+      health_url: config.health_url.clone(),
+      mapping: config.mapping.clone(),
+      encoding: config.value_encoding,
+      headers: default_headers(&config.http)?,
+      exchange: RefCell::new(None),
+    })
+  }
+
+  /// Sends parameters in JSON format to specified URL and parses the JSON response.
+  /// When the operation is mapped, the request is built from template
+  /// and the result and errors are extracted from the response.
+  fn send<P: Serialize, R: DeserializeOwned>(
+    &self,
+    url: &str,
+    params: &P,
+    mapping: Option<&OperationMapping>,
+  ) -> Result<ResultDto<R>> {
+    let params = serde_json::to_value(params).map_err(|reason| SerializingFailed(reason.to_string()))?;
+    self.send_context(url, &params, &params, mapping)
+  }
+
+  /// Sends parameters to specified URL and parses the JSON response.
+  /// Parameters are sent unchanged when the operation is not mapped,
+  /// otherwise the request and the URL are rendered from specified context.
+  fn send_context<R: DeserializeOwned>(
+    &self,
+    url: &str,
+    params: &Value,
+    context: &Value,
+    mapping: Option<&OperationMapping>,
+  ) -> Result<ResultDto<R>> {
+    let method = match mapping.and_then(|m| m.method.as_deref()) {
+      Some(method) => Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| InvalidConfiguration(format!("invalid HTTP method '{}'", method)))?,
+      None => Method::POST,
+    };
+    let body = match mapping.and_then(|m| m.request.as_ref()) {
+      Some(template) => render(template, context),
+      None => params.clone(),
+    };
+    let request = self
+      .client
+      .request(method, &render_url(url, context))
+      .json(&body)
+      .build()
+      .map_err(|reason| TransportFailed(reason.to_string()))?;
+    let mut exchange = Exchange {
+      request: raw_request(&request, &self.headers),
+      response: String::new(),
+    };
+    let response = self.client.execute(request);
+    let text = response
+      .map_err(|reason| TransportFailed(reason.to_string()))
+      .and_then(|response| {
+        exchange.response = raw_response(&response);
+        response.text().map_err(|reason| TransportFailed(reason.to_string()))
+      });
+    if let Ok(text) = &text {
+      exchange.response.push_str(text);
+    }
+    self.exchange.replace(Some(exchange));
+    let response = serde_json::from_str::<Value>(&text?)
+      .map_err(|reason| InvalidResponse(format!("error decoding response body: {}", reason)))?;
+    extract(&response, mapping)
+  }
+}
+
+impl Engine for RestEngine {
+  fn deploy(&mut self, params: &DeployParams) -> Result<ResultDto<DeployResult>> {
+    self.send(&self.deploy_url, params, self.mapping.deploy.as_ref())
+  }
+
+  /// Besides default parameters, input values keyed by input name
+  /// are available in templates under `/inputs` pointer, they are not sent in default requests.
+  fn evaluate(&mut self, params: &EvaluateParams) -> Result<ResultDto<ActualValueDto>> {
+    let params = encode_params(params, self.encoding)?;
+    let mut context = params.clone();
+    if let Value::Object(members) = &mut context {
+      let inputs = match members.get("input") {
+        Some(Value::Array(input)) => input
+          .iter()
+          .map(|input_node| {
+            (
+              input_node["name"].as_str().unwrap_or_default().to_string(),
+              input_node["value"].clone(),
+            )
+          })
+          .collect(),
+        _ => serde_json::Map::new(),
+      };
+      members.insert("inputs".to_string(), Value::Object(inputs));
+    }
+    decode_result(
+      self.send_context(&self.evaluate_url, &params, &context, self.mapping.evaluate.as_ref())?,
+      self.encoding,
+    )
+  }
+
+  fn undeploy(&mut self, params: &UndeployParams) -> Result<ResultDto<UndeployResult>> {
+    match &self.undeploy_url {
+      Some(undeploy_url) => self.send(undeploy_url, params, self.mapping.undeploy.as_ref()),
+      None => Err(NotSupported("undeploying models".to_string())),
+    }
+  }
+
+  fn verify(&mut self, params: &VerifyParams) -> Result<bool> {
+    match &self.verify_url {
+      Some(verify_url) => Ok(
+        self
+          .send::<_, DeployResult>(verify_url, params, self.mapping.verify.as_ref())?
+          .data
+          .is_some(),
+      ),
+      None => Ok(true),
+    }
+  }
+
+  /// The engine is healthy when the health endpoint responds with success status,
+  /// without health endpoint the engine is assumed to be healthy.
+  fn health(&mut self) -> Result<()> {
+    match &self.health_url {
+      Some(health_url) => {
+        let response = self
+          .client
+          .get(health_url)
+          .send()
+          .map_err(|reason| TransportFailed(reason.to_string()))?;
+        if response.status().is_success() {
+          Ok(())
+        } else {
+          Err(TransportFailed(format!("engine is not healthy: {}", response.status())))
+        }
+      }
+      None => Ok(()),
+    }
+  }
+
+  fn take_exchange(&mut self) -> Option<Exchange> {
+    self.exchange.take()
+  }
+}
+
+/// Returns the raw HTTP request line, headers and body.
+/// Values of default headers (configured headers and authorization) are hidden, as they may contain credentials.
+fn raw_request(request: &Request, headers: &HeaderMap) -> String {
+  let url = request.url();
+  let mut raw = format!("{} {}", request.method(), url.path());
+  if let Some(query) = url.query() {
+    raw.push_str(&format!("?{}", query));
+  }
+  raw.push_str(" HTTP/1.1\r\n");
+  if let Some(host) = url.host_str() {
+    match url.port() {
+      Some(port) => raw.push_str(&format!("host: {}:{}\r\n", host, port)),
+      None => raw.push_str(&format!("host: {}\r\n", host)),
+    }
+  }
+  for name in headers.keys() {
+    raw.push_str(&format!("{}: <hidden>\r\n", name));
+  }
+  for (name, value) in request.headers() {
+    raw.push_str(&format!("{}: {}\r\n", name, value.to_str().unwrap_or("<binary>")));
+  }
+  raw.push_str("\r\n");
+  if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+    raw.push_str(&String::from_utf8_lossy(body));
+  }
+  raw
+}
+
+/// Returns the raw HTTP status line and headers of the response.
+fn raw_response(response: &Response) -> String {
+  let mut raw = format!("{:?} {}\r\n", response.version(), response.status());
+  for (name, value) in response.headers() {
+    raw.push_str(&format!("{}: {}\r\n", name, value.to_str().unwrap_or("<binary>")));
+  }
+  raw.push_str("\r\n");
+  raw
+}
+
+/// Builds HTTP client with headers, authentication and TLS settings.
+pub fn build_client(http: &HttpParams) -> Result<Client> {
+  let mut builder = Client::builder()
+    .default_headers(default_headers(http)?)
+    .danger_accept_invalid_certs(http.accept_invalid_certificates);
+  if let Some(timeout_ms) = http.timeout_ms {
+    builder = builder.timeout(Duration::from_millis(timeout_ms));
+  }
+  if let Some(file_path) = &http.ca_certificate_file_path {
+    let certificate = Certificate::from_pem(&read_file(file_path)?)
+      .map_err(|reason| InvalidConfiguration(format!("invalid CA certificate '{}': {}", file_path, reason)))?;
+    builder = builder.add_root_certificate(certificate);
+  }
+  if let Some(file_path) = &http.client_identity_file_path {
+    let password = http.client_identity_password.as_deref().unwrap_or("");
+    let identity = Identity::from_pkcs12_der(&read_file(file_path)?, password)
+      .map_err(|reason| InvalidConfiguration(format!("invalid client identity '{}': {}", file_path, reason)))?;
+    builder = builder.identity(identity);
+  }
+  builder
+    .build()
+    .map_err(|reason| InvalidConfiguration(format!("building HTTP client failed: {}", reason)))
+}
+
+/// Prepares headers sent with every request. Authorization header built
+/// from bearer token or basic authentication credentials replaces
+/// the authorization header specified explicitly.
+pub fn default_headers(http: &HttpParams) -> Result<HeaderMap> {
+  let mut headers = HeaderMap::new();
+  for (name, value) in &http.headers {
+    headers.insert(
+      HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| InvalidConfiguration(format!("invalid header name '{}'", name)))?,
+      header_value(name, value)?,
+    );
+  }
+  if let Some(user) = &http.basic_auth_user {
+    let credentials = format!("{}:{}", user, http.basic_auth_password.as_deref().unwrap_or(""));
+    headers.insert(
+      AUTHORIZATION,
+      header_value(
+        AUTHORIZATION.as_str(),
+        &format!("Basic {}", base64::encode(credentials)),
+      )?,
+    );
+  }
+  if let Some(token) = &http.bearer_token {
+    headers.insert(
+      AUTHORIZATION,
+      header_value(AUTHORIZATION.as_str(), &format!("Bearer {}", token))?,
+    );
+  }
+  Ok(headers)
+}
+
+/// Converts text into the value of the header with specified name.
+/// The value is not included in the error message, as it may contain credentials.
+fn header_value(name: &str, value: &str) -> Result<HeaderValue> {
+  HeaderValue::from_str(value).map_err(|_| InvalidConfiguration(format!("invalid value of header '{}'", name)))
+}
+
+/// Reads the content of the file containing certificates.
+fn read_file(file_path: &str) -> Result<Vec<u8>> {
+  fs::read(file_path).map_err(|reason| ReadingFileFailed(format!("{}: {}", file_path, reason)))
+}
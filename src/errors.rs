@@ -16,21 +16,87 @@
 
 //! Result and error definitions.
 
+use std::fmt;
+
+/// Result returned by the runner.
 pub type Result<T, E = RunnerError> = std::result::Result<T, E>;
 
+/// Errors reported by the runner.
 #[derive(Debug, PartialEq)]
 pub enum RunnerError {
+  /// Input/output operation failed.
   IOError(String),
+  /// Reading the file failed.
   ReadingFileFailed(String),
+  /// Parsing the XML document failed.
   ParsingXMLFailed(String),
+  /// Validating the XML document against the schema failed with specified status.
   ValidatingXMLFailed(i32),
+  /// Mandatory XML node with specified name is missing.
   XmlExpectedMandatoryNode(String),
+  /// Mandatory text content of the XML node with specified name is missing.
   XmlExpectedMandatoryTextContent(String),
+  /// Mandatory XML attribute with specified name is missing.
   XmlExpectedMandatoryAttribute(String),
+  /// Deploying the model failed.
   DeploymentFailed(String),
+  /// Removing the deployed model failed.
+  UndeploymentFailed(String),
+  /// Parsing the JSON document failed.
+  ParsingJSONFailed(String),
+  /// Parsing the YAML document failed.
+  ParsingYAMLFailed(String),
+  /// Serializing the value failed.
+  SerializingFailed(String),
+  /// Format of the file with specified name is not supported.
+  UnsupportedFileFormat(String),
+  /// Casting the value to the specified type failed.
+  CastFailed(String),
+  /// Imported namespace is not defined in any model.
+  ImportNotFound(String),
+  /// Models with specified file names import each other.
+  CyclicImports(String),
+  /// Sending the request to the engine or receiving the response failed.
+  TransportFailed(String),
+  /// Response received from the engine is not valid.
+  InvalidResponse(String),
+  /// Request does not match the recorded request being replayed.
+  ReplayMismatch(String),
+  /// Operation is not supported by the engine.
+  NotSupported(String),
+  /// Configuration is not valid.
+  InvalidConfiguration(String),
+}
+
+impl fmt::Display for RunnerError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::IOError(reason) => write!(f, "input/output error: {}", reason),
+      Self::ReadingFileFailed(reason) => write!(f, "reading file failed: {}", reason),
+      Self::ParsingXMLFailed(reason) => write!(f, "parsing XML failed: {}", reason),
+      Self::ValidatingXMLFailed(status) => write!(f, "validating XML failed with status {}", status),
+      Self::XmlExpectedMandatoryNode(name) => write!(f, "expected mandatory node '{}'", name),
+      Self::XmlExpectedMandatoryTextContent(name) => write!(f, "expected mandatory text content in node '{}'", name),
+      Self::XmlExpectedMandatoryAttribute(name) => write!(f, "expected mandatory attribute '{}'", name),
+      Self::DeploymentFailed(reason) => write!(f, "deployment failed: {}", reason),
+      Self::UndeploymentFailed(reason) => write!(f, "undeployment failed: {}", reason),
+      Self::ParsingJSONFailed(reason) => write!(f, "parsing JSON failed: {}", reason),
+      Self::ParsingYAMLFailed(reason) => write!(f, "parsing YAML failed: {}", reason),
+      Self::SerializingFailed(reason) => write!(f, "serializing failed: {}", reason),
+      Self::UnsupportedFileFormat(file_name) => write!(f, "unsupported file format: {}", file_name),
+      Self::CastFailed(reason) => write!(f, "cast failed: {}", reason),
+      Self::ImportNotFound(reason) => write!(f, "import not found: {}", reason),
+      Self::CyclicImports(file_names) => write!(f, "cyclic imports in models: {}", file_names),
+      Self::TransportFailed(reason) => write!(f, "transport failed: {}", reason),
+      Self::InvalidResponse(reason) => write!(f, "invalid response: {}", reason),
+      Self::ReplayMismatch(reason) => write!(f, "replay mismatch: {}", reason),
+      Self::NotSupported(operation) => write!(f, "not supported: {}", operation),
+      Self::InvalidConfiguration(reason) => write!(f, "invalid configuration: {}", reason),
+    }
+  }
 }
 
-// TODO Implement Display trait to make error reporting more verbose and user friendly.
+impl std::error::Error for RunnerError {}
 
 impl From<std::io::Error> for RunnerError {
   fn from(err: std::io::Error) -> Self {
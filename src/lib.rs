@@ -0,0 +1,49 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Library for running Decision Model and Notation™ Technology Compatibility Kit tests.
+//!
+//! The library provides:
+//! - [model] - test cases read from TCK files, [writer] and [suite] for writing and converting them,
+//! - [dto] - data transfer objects exchanged with the tested engine,
+//! - [compare] - comparison of actual values with expected values,
+//! - [engine] - adapters for tested engines,
+//! - [runner] - runner executing test cases against the tested engine, or benchmarking the engine.
+
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate serde_derive;
+extern crate regex;
+extern crate serde_json;
+
+pub mod cache;
+pub mod compare;
+pub mod config;
+pub mod definitions;
+pub mod dto;
+pub mod encoding;
+pub mod engine;
+pub mod errors;
+pub mod model;
+pub mod params;
+pub mod results;
+pub mod runner;
+pub mod suite;
+#[cfg(test)]
+mod tests;
+pub mod validator;
+pub mod writer;
@@ -15,280 +15,71 @@
  */
 
 //! Runner for Decision Model and Notation™ Technology Compatibility Kit written in Rust.
+//!
+//! Command line interface of the runner, see the [dmn_tck] library for details.
 
-#[macro_use]
-extern crate lazy_static;
-#[macro_use]
-extern crate serde_derive;
-extern crate regex;
-extern crate serde_json;
-
-use std::fs;
+use dmn_tck::errors::Result;
+use dmn_tck::runner::{BenchmarkReport, ConsoleListener, CsvReport, DeploymentReport, Runner};
+use dmn_tck::{config, suite};
 use std::path::Path;
-
-use http::Uri;
-use reqwest::blocking::Client;
-
-use crate::dto::{ActualValueDto, InputNodeDto, ValueDto};
-use crate::errors::{Result, RunnerError};
-use crate::model::parse_from_file;
-use crate::params::{DeployParams, EvaluateParams};
-use crate::results::{DeployResult, ResultDto};
-use crate::validator::validate_test_cases_file;
-use regex::Regex;
-use std::fs::File;
-use std::io::{BufWriter, Write};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-
-mod config;
-mod dto;
-mod errors;
-mod model;
-mod params;
-mod results;
-#[cfg(test)]
-mod tests;
-mod validator;
-
-static SUCCESS_COUNT: AtomicU64 = AtomicU64::new(0);
-static FAILURE_COUNT: AtomicU64 = AtomicU64::new(0);
-static OTHER_COUNT: AtomicU64 = AtomicU64::new(0);
-static STOP_ON_FAILURE: AtomicBool = AtomicBool::new(false);
-
-const ORDERING: Ordering = Ordering::SeqCst;
-
-/// Main entrypoint of the runner.
-fn main() -> Result<()> {
-  let config = config::get();
-  let pattern = config.file_name_pattern;
-  STOP_ON_FAILURE.fetch_or(config.stop_on_failure, ORDERING);
+use std::process::ExitCode;
+
+/// Main entrypoint of the runner, exits with failure status when the run was cancelled.
+fn main() -> Result<ExitCode> {
+  let args: Vec<String> = std::env::args().collect();
+  if args.len() == 4 && args[1] == "convert" {
+    println!("Converting {} into {}", args[2], args[3]);
+    suite::convert(&args[2], &args[3])?;
+    return Ok(ExitCode::SUCCESS);
+  }
+  let config = config::load(config_file_name(&args))?;
   let dir_path = Path::new(&config.test_cases_dir_path);
   if dir_path.exists() && dir_path.is_dir() {
-    println!("Starting DMN TCK runner...");
-    let client = reqwest::blocking::Client::new();
-    println!("Searching DMN files in directory: {}", dir_path.display());
-    let dmn_files = sorted_files(dir_path, "dmn", &pattern)?;
-    for dmn_file in &dmn_files {
-      deploy_dmn_definitions(dmn_file, &client, &config.deploy_url)?;
+    if let Some(benchmark) = &config.benchmark {
+      let report = BenchmarkReport::new(&benchmark.report_file_path, benchmark.report_format)?;
+      let mut runner = Runner::new(config)?;
+      runner.add_listener(Box::new(ConsoleListener));
+      runner.add_listener(Box::new(report));
+      return Ok(exit_code(runner.benchmark()?.cancelled));
     }
-    println!("\n\nDeployed {} *.dmn files.\n", dmn_files.len());
-    let mut writer = get_writer();
-    let xml_files = sorted_files(dir_path, "xml", &pattern)?;
-    for xml_file in &xml_files {
-      execute_tests(&mut writer, xml_file, &client, &config.evaluate_url)?;
-    }
-    println!("Processed {} *.xml files.", xml_files.len());
-    writer.flush().expect("flushing output file failed");
-    let success_count = SUCCESS_COUNT.load(ORDERING);
-    let failure_count = FAILURE_COUNT.load(ORDERING);
-    let other_count = OTHER_COUNT.load(ORDERING);
-    let total_count = success_count + failure_count + other_count;
-    let (success_perc, failure_perc, other_perc) = if total_count > 0 {
-      (
-        (success_count * 100) as f64 / total_count as f64,
-        (failure_count * 100) as f64 / total_count as f64,
-        (other_count * 100) as f64 / total_count as f64,
-      )
-    } else {
-      (0., 0., 0.)
+    let resume = args.iter().any(|arg| arg == "--resume");
+    let report = CsvReport::new(&config.report_file_path)?;
+    let deployment_report = match &config.deployment_report_file_path {
+      Some(file_path) => Some(DeploymentReport::new(file_path)?),
+      None => None,
     };
-    println!("-----------------");
-    println!("    Total: {:>4}", total_count);
-    println!("  Success: {:>4}{:>6.1}%", success_count, success_perc);
-    println!("  Failure: {:>4}{:>6.1}%", failure_count, failure_perc);
-    println!("    Other: {:>4}{:>6.1}%", other_count, other_perc);
+    let mut runner = Runner::new(config)?;
+    runner.add_listener(Box::new(ConsoleListener));
+    runner.add_listener(Box::new(report));
+    if let Some(deployment_report) = deployment_report {
+      runner.add_listener(Box::new(deployment_report));
+    }
+    let summary = if resume { runner.resume()? } else { runner.run()? };
+    Ok(exit_code(summary.cancelled))
   } else {
     usage();
+    Ok(ExitCode::SUCCESS)
   }
-  Ok(())
-}
-
-fn deploy_dmn_definitions(dmn_file: &str, client: &Client, deploy_url: &str) -> Result<()> {
-  if let Ok(source) = Uri::builder()
-    .scheme("file")
-    .authority("localhost")
-    .path_and_query(dmn_file)
-    .build()
-  {
-    println!("\nDeploying: {}", source);
-    if let Ok(content) = fs::read_to_string(dmn_file) {
-      let params = DeployParams {
-        source: Some(source.to_string()),
-        content: Some(base64::encode(content)),
-        tag: Some(file_name(dmn_file)),
-      };
-      match client.post(deploy_url).json(&params).send() {
-        Ok(response) => match response.json::<ResultDto<DeployResult>>() {
-          Ok(result) => {
-            if let Some(data) = result.data {
-              println!(
-                "SUCCESS\n    name: {}\n      id: {}\n     tag: {}",
-                data.name.unwrap_or_else(|| "(no value)".to_string()),
-                data.id.unwrap_or_else(|| "(no value)".to_string()),
-                data.tag.unwrap_or_else(|| "(no value)".to_string())
-              )
-            } else if result.errors.is_some() {
-              return Err(RunnerError::DeploymentFailed(result.errors_as_string()));
-            } else {
-              return Err(RunnerError::DeploymentFailed(format!("{:?}", result)));
-            }
-          }
-          Err(reason) => {
-            return Err(RunnerError::DeploymentFailed(format!("{:?}", reason)));
-          }
-        },
-        Err(reason) => {
-          return Err(RunnerError::DeploymentFailed(format!("{:?}", reason)));
-        }
-      }
-    }
-  }
-  Ok(())
-}
-
-fn execute_tests(writer: &mut BufWriter<File>, file_name: &str, client: &Client, evaluate_url: &str) -> Result<()> {
-  println!("\nProcessing file: {}", file_name);
-  print!("Validating...");
-  validate_test_cases_file(file_name)?;
-  print!("OK");
-  print!(",  Parsing...");
-  let test_cases = parse_from_file(file_name)?;
-  println!("OK");
-  let empty_id = String::new();
-  for test_case in &test_cases.test_cases {
-    let test_id = test_case.id.as_ref().unwrap_or(&empty_id);
-    for result_node in &test_case.result_nodes {
-      let name = &result_node.name;
-      let artifact = result_node.typ.to_string();
-      println!(
-        "Executing test case: {}, result name: '{}', artifact: '{}'",
-        test_id, name, artifact
-      );
-      let params = EvaluateParams {
-        tag: test_cases.model_name.clone(),
-        artifact,
-        name: name.clone(),
-        input: test_case.input_nodes.iter().map(InputNodeDto::from).collect(),
-      };
-      match client.post(evaluate_url).json(&params).send() {
-        Ok(response) => match response.json::<ResultDto<ActualValueDto>>() {
-          Ok(result) => {
-            // println!("{:?}", result);
-            if let Some(data) = result.data {
-              if let Some(actual_dto) = data.value {
-                if let Some(expected) = &result_node.expected {
-                  let expected_dto = ValueDto::from(expected);
-                  if actual_dto == expected_dto {
-                    write_line(writer, file_name, &test_id, "SUCCESS", "");
-                  } else {
-                    eprintln!("  actual: {:?}", actual_dto);
-                    eprintln!("expected: {:?}", expected_dto);
-                    write_line(writer, file_name, &test_id, "FAILURE", "actual <> expected");
-                  }
-                } else {
-                  write_line(writer, file_name, &test_id, "FAILURE", "no expected value");
-                }
-              } else {
-                write_line(writer, file_name, &test_id, "FAILURE", "no actual value");
-              }
-            } else if result.errors.is_some() {
-              write_line(writer, file_name, &test_id, "FAILURE", &result.errors_as_string());
-            } else {
-              write_line(writer, file_name, &test_id, "FAILURE", format!("{:?}", result).as_str());
-            }
-          }
-          Err(reason) => {
-            write_line(writer, file_name, &test_id, "FAILURE", &reason.to_string());
-          }
-        },
-        Err(reason) => {
-          write_line(writer, file_name, &test_id, "FAILURE", &reason.to_string());
-        }
-      }
-    }
-  }
-  Ok(())
 }
 
-fn write_line(writer: &mut BufWriter<File>, test_file_name: &str, test_id: &str, test_result: &str, remarks: &str) {
-  writeln!(
-    writer,
-    r#""{}","{}","{}","{}","{}""#,
-    dir_name(test_file_name),
-    file_name(test_file_name),
-    test_id,
-    test_result,
-    remarks
-  )
-  .unwrap();
-  match test_result.to_lowercase().as_str() {
-    "failure" => {
-      FAILURE_COUNT.fetch_add(1, ORDERING);
-      eprintln!("FAILURE: {}", remarks);
-      if STOP_ON_FAILURE.load(ORDERING) {
-        std::process::exit(1);
-      }
-    }
-    "success" => {
-      SUCCESS_COUNT.fetch_add(1, ORDERING);
-      println!("SUCCESS");
-    }
-    _ => {
-      OTHER_COUNT.fetch_add(1, ORDERING);
-      println!("{}: {}", test_result, remarks);
-    }
+/// Returns the name of the configuration file passed as the only argument (options starting with `--` are skipped),
+/// `runner.yml` is used when no file is specified.
+fn config_file_name(args: &[String]) -> &str {
+  let args: Vec<&String> = args.iter().filter(|arg| !arg.starts_with("--")).collect();
+  if args.len() == 2 {
+    args[1]
+  } else {
+    "runner.yml"
   }
 }
 
-fn get_writer() -> BufWriter<File> {
-  let file = File::create("report.csv").expect("creating output file failed");
-  BufWriter::new(file)
-}
-
-fn sorted_files(path: &Path, ext: &str, pattern: &str) -> Result<Vec<String>> {
-  let mut files = search_files(path, ext, pattern)?;
-  files.sort();
-  Ok(files)
-}
-
-fn search_files(path: &Path, ext: &str, pattern: &str) -> Result<Vec<String>> {
-  let mut files = vec![];
-  if let Ok(entries) = fs::read_dir(path) {
-    for entry in entries {
-      if let Ok(entry) = entry {
-        let path = entry.path();
-        if path.is_dir() {
-          files.append(search_files(&path, ext, pattern)?.as_mut());
-        } else if let Some(extension) = path.extension() {
-          if extension == ext {
-            let file_name = path.canonicalize().unwrap().display().to_string();
-            if pattern.is_empty() {
-              files.push(file_name);
-            } else {
-              if let Ok(re) = Regex::new(pattern) {
-                if re.is_match(&file_name) {
-                  files.push(file_name);
-                }
-              } else {
-                println!("Invalid matching pattern: '{}'", pattern)
-              }
-            }
-          }
-        }
-      }
-    }
+/// Returns the exit code of the run.
+fn exit_code(cancelled: bool) -> ExitCode {
+  if cancelled {
+    ExitCode::FAILURE
+  } else {
+    ExitCode::SUCCESS
   }
-  Ok(files)
-}
-
-/// Retrieves the parent path without file name from given `name`.
-pub fn dir_name(name: &str) -> String {
-  Path::new(name).parent().unwrap().to_str().unwrap().to_string()
-}
-
-/// Retrieves the file name with extension from given `name`.
-pub fn file_name(name: &str) -> String {
-  Path::new(name).file_name().unwrap().to_str().unwrap().to_string()
 }
 
 /// Displays usage message.
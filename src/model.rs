@@ -19,6 +19,7 @@
 use crate::errors::RunnerError;
 use crate::errors::RunnerError::*;
 use roxmltree::Node;
+use std::fmt;
 use std::fs::read_to_string;
 
 const XSI: &str = "http://www.w3.org/2001/XMLSchema-instance";
@@ -47,18 +48,24 @@ const ATTR_NIL: &str = "nil";
 const ATTR_TYPE: &str = "type";
 
 /// Test cases.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct TestCases {
+  /// Optional name of the model file the test cases are defined for.
   pub model_name: Option<String>,
+  /// Labels of test cases.
   pub labels: Vec<String>,
+  /// Test cases.
   pub test_cases: Vec<TestCase>,
 }
 
 /// Type of the test case.
 #[derive(Debug, PartialEq)]
 pub enum TestCaseType {
+  /// Test case evaluating a decision.
   Decision,
+  /// Test case invoking a business knowledge model.
   BusinessKnowledgeModel,
+  /// Test case invoking a decision service.
   DecisionService,
 }
 
@@ -66,7 +73,7 @@ impl From<String> for TestCaseType {
   fn from(value: String) -> Self {
     match value.to_lowercase().trim() {
       "bkm" => Self::BusinessKnowledgeModel,
-      "decisionService" => Self::DecisionService,
+      "decisionservice" => Self::DecisionService,
       _ => Self::Decision,
     }
   }
@@ -81,19 +88,22 @@ impl From<Option<String>> for TestCaseType {
   }
 }
 
-impl ToString for TestCaseType {
-  fn to_string(&self) -> String {
-    match self {
-      TestCaseType::Decision => "decision",
-      TestCaseType::BusinessKnowledgeModel => "bkm",
-      TestCaseType::DecisionService => "decisionService",
-    }
-    .to_string()
+impl fmt::Display for TestCaseType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        TestCaseType::Decision => "decision",
+        TestCaseType::BusinessKnowledgeModel => "bkm",
+        TestCaseType::DecisionService => "decisionService",
+      }
+    )
   }
 }
 
 /// Single test case.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct TestCase {
   /// Optional identifier of this [TestCase].
   pub id: Option<String>,
@@ -112,7 +122,7 @@ pub struct TestCase {
 }
 
 /// Input node defined for test case.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct InputNode {
   /// Required name of this [InputNode].
   pub name: String,
@@ -121,29 +131,38 @@ pub struct InputNode {
 }
 
 /// Result node defined for the test case.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ResultNode {
+  /// Name of the evaluated decision, decision service or business knowledge model.
   pub name: String,
+  /// Flag indicating if the evaluation is expected to fail.
   pub error_result: bool,
+  /// Type of the result node.
   pub typ: TestCaseType,
+  /// Optional type the actual value is casted to before comparison.
   pub cast: Option<String>,
+  /// Optional expected value.
   pub expected: Option<Value>,
+  /// Optional value computed by the engine.
   pub computed: Option<Value>,
 }
 
 /// Types of values.
-/// [ValueType] may be a single value,
+/// [Value] may be a single value,
 /// collection of components
 /// or a list.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Value {
+  /// Simple value.
   Simple(Simple),
+  /// Components of the context.
   Components(Vec<Component>),
+  /// List of values.
   List(List),
 }
 
 /// Value representing simple result of the test case.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Simple {
   /// Type of the value in namespace-prefixed form.
   pub typ: Option<String>,
@@ -154,7 +173,7 @@ pub struct Simple {
 }
 
 /// Value representing complex result of a test case.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Component {
   /// Optional name of this component.
   pub name: Option<String>,
@@ -165,7 +184,7 @@ pub struct Component {
 }
 
 /// Value representing a list.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct List {
   /// Vector of list items (values), may be empty.
   pub items: Vec<Value>,
@@ -193,7 +212,7 @@ pub fn parse_from_file(file_name: &str) -> Result<TestCases, RunnerError> {
 
 /// Parses the XML content from string.
 pub fn parse_from_string(s: &str) -> Result<TestCases, RunnerError> {
-  match roxmltree::Document::parse(&s) {
+  match roxmltree::Document::parse(s) {
     Ok(document) => {
       let test_cases_node = document.root_element();
       if test_cases_node.tag_name().name() != NODE_TEST_CASES {
@@ -270,7 +289,7 @@ fn parse_result_nodes(node: &Node) -> Result<Vec<ResultNode>, RunnerError> {
   for ref result_node in node.children().filter(|n| n.tag_name().name() == NODE_RESULT_NODE) {
     items.push(ResultNode {
       name: required_attribute(result_node, ATTR_NAME)?,
-      error_result: optional_attribute(result_node, ATTR_ERROR_RESULT).map_or(false, |v| v == "true"),
+      error_result: optional_attribute(result_node, ATTR_ERROR_RESULT).as_deref() == Some("true"),
       typ: optional_attribute(result_node, ATTR_TYPE).into(),
       cast: optional_attribute(result_node, ATTR_CAST),
       expected: parse_child_value_type(result_node, NODE_EXPECTED),
@@ -321,7 +340,7 @@ fn parse_simple_value(node: &Node) -> Option<Simple> {
   None
 }
 
-/// Parses a collection of component values.
+/// Parses a collection of component values, the document order of components is preserved.
 fn parse_value_components(node: &Node) -> Option<Vec<Component>> {
   let mut items = vec![];
   for ref component_node in node.children().filter(|n| n.tag_name().name() == NODE_COMPONENT) {
@@ -332,7 +351,6 @@ fn parse_value_components(node: &Node) -> Option<Vec<Component>> {
     })
   }
   if !items.is_empty() {
-    items.sort_by(|a, b| a.name.cmp(&b.name));
     return Some(items);
   }
   None
@@ -366,25 +384,19 @@ fn required_attribute(node: &Node, attr_name: &str) -> Result<String, RunnerErro
 
 /// XML utility function that returns the value of the optional attribute.
 fn optional_attribute(node: &Node, attr_name: &str) -> Option<String> {
-  if let Some(attr_value) = node.attribute(attr_name) {
-    Some(attr_value.to_string())
-  } else {
-    None
-  }
+  node.attribute(attr_name).map(|attr_value| attr_value.to_string())
 }
 
 /// XML utility function that returns the value of the optional `xsi:type` attribute.
 fn optional_xsi_type_attribute(node: &Node) -> Option<String> {
-  if let Some(attr_value) = node.attribute((XSI, ATTR_TYPE)) {
-    Some(attr_value.to_string())
-  } else {
-    None
-  }
+  node
+    .attribute((XSI, ATTR_TYPE))
+    .map(|attr_value| attr_value.to_string())
 }
 
 /// XML utility function that returns `true` when `xsi:nil="true"` attribute is specified.
 fn optional_nil_attribute(node: &Node) -> bool {
-  node.attribute((XSI, ATTR_NIL)).map_or(false, |v| v == "true")
+  node.attribute((XSI, ATTR_NIL)) == Some("true")
 }
 
 /// XML utility function that returns required textual content from the specified node.
@@ -398,11 +410,7 @@ fn required_content(node: &Node) -> Result<String, RunnerError> {
 
 /// XML utility function that returns optional textual content of the node.
 fn optional_content(node: &Node) -> Option<String> {
-  if let Some(text) = node.text() {
-    Some(text.to_string())
-  } else {
-    None
-  }
+  node.text().map(|text| text.to_string())
 }
 
 /// XML utility function that returns the required textual content from the optional child node.
@@ -1,4 +1,6 @@
-use crate::dto::InputNodeDto;
+//! Parameters of requests sent to the engine.
+
+use crate::dto::{InputNodeDto, ValueDto};
 
 /// Parameters for deploying definitions from *.dmn files.
 #[derive(Serialize)]
@@ -14,19 +16,47 @@ pub struct DeployParams {
   pub tag: Option<String>,
 }
 
+/// Parameters for verifying if the model deployed earlier is still available.
+#[derive(Serialize)]
+pub struct VerifyParams {
+  /// Identifier of the deployed model, returned by the engine after deployment.
+  #[serde(rename = "id")]
+  pub id: Option<String>,
+  /// Unique tag associated with the deployed model.
+  #[serde(rename = "tag")]
+  pub tag: Option<String>,
+}
+
+/// Parameters for removing deployed definitions.
+#[derive(Serialize)]
+pub struct UndeployParams {
+  /// Unique tag associated with the deployed model.
+  #[serde(rename = "tag")]
+  pub tag: Option<String>,
+}
+
 /// Parameters for evaluating decision artifact.
 #[derive(Serialize)]
 pub struct EvaluateParams {
   /// Tag of the model where the artifact will be searched.
   #[serde(rename = "tag")]
   pub tag: Option<String>,
-  /// Type of decision artifact to be evaluated.
+  /// Type of decision artifact to be evaluated: `decision`, `bkm` or `decisionService`.
   #[serde(rename = "artifact")]
   pub artifact: String,
-  /// Name of the artifact to be evaluated.
+  /// Name of the artifact to be evaluated, for invocables this is the invocable name.
   #[serde(rename = "name")]
   pub name: String,
-  /// Input values.
+  /// Input values, for invocables these are named parameters.
   #[serde(rename = "input")]
   pub input: Vec<InputNodeDto>,
+  /// Positional parameters for invoking business knowledge models, in order of input nodes.
+  #[serde(rename = "arguments", skip_serializing_if = "Option::is_none")]
+  pub arguments: Option<Vec<Option<ValueDto>>>,
+  /// Name of the result node, sent only when invoking business knowledge models and decision services.
+  #[serde(rename = "resultName", skip_serializing_if = "Option::is_none")]
+  pub result_name: Option<String>,
+  /// Optional type the result should be cast to before returning.
+  #[serde(rename = "cast", skip_serializing_if = "Option::is_none")]
+  pub cast: Option<String>,
 }
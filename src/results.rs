@@ -1,5 +1,7 @@
+//! Results returned by the engine.
+
 /// Data transfer object for an error.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorDto {
   /// Error details.
   #[serde(rename = "details")]
@@ -7,7 +9,7 @@ pub struct ErrorDto {
 }
 
 /// Data transfer object for a result.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ResultDto<T> {
   /// Result containing data.
   #[serde(rename = "data")]
@@ -18,17 +20,18 @@ pub struct ResultDto<T> {
 }
 
 impl<T> ResultDto<T> {
+  /// Returns details of all errors separated with commas.
   pub fn errors_as_string(&self) -> String {
     self
       .errors
       .as_ref()
       .map(|v| v.iter().map(|e| e.details.clone()).collect::<Vec<String>>().join(", "))
-      .unwrap_or_else(|| "".to_string())
+      .unwrap_or_default()
   }
 }
 
 /// Result received after successful model deployment.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DeployResult {
   /// Unique name of the deployed model.
   #[serde(rename = "name")]
@@ -40,3 +43,11 @@ pub struct DeployResult {
   #[serde(rename = "tag")]
   pub tag: Option<String>,
 }
+
+/// Result received after successful removal of the deployed model.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndeployResult {
+  /// Unique tag associated with the removed model.
+  #[serde(rename = "tag")]
+  pub tag: Option<String>,
+}
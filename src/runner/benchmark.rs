@@ -0,0 +1,441 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Benchmark measuring latency and throughput of evaluations, and the listener writing benchmark reports.
+
+use crate::compare::CompareOptions;
+use crate::config::{BenchmarkFormat, BenchmarkParams};
+use crate::definitions::model_tag;
+use crate::engine::Engine;
+use crate::errors::{Result, RunnerError};
+use crate::model::ResultNode;
+use crate::params::EvaluateParams;
+use crate::runner::csv::quote;
+use crate::runner::events::{Event, Listener, TestOutcome, TestStatus};
+use crate::runner::{
+  check_result, dir_name, evaluate_params, file_name, millis, pending_outcome, relative_path, CancellationToken,
+  Deployments, TestFile,
+};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Latency statistics of evaluations.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LatencyStats {
+  /// Number of samples.
+  pub count: usize,
+  /// Minimum latency.
+  pub min: Duration,
+  /// Median latency.
+  pub median: Duration,
+  /// 95th percentile of latency.
+  pub p95: Duration,
+  /// 99th percentile of latency.
+  pub p99: Duration,
+  /// Maximum latency.
+  pub max: Duration,
+}
+
+impl LatencyStats {
+  /// Computes statistics of latency samples, returns `None` when there are no samples.
+  /// Percentiles are computed using the nearest-rank method.
+  pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    Some(Self {
+      count: sorted.len(),
+      min: *sorted.first()?,
+      median: percentile(&sorted, 50.0),
+      p95: percentile(&sorted, 95.0),
+      p99: percentile(&sorted, 99.0),
+      max: *sorted.last()?,
+    })
+  }
+}
+
+/// Returns the percentile of sorted samples, samples must not be empty.
+fn percentile(sorted: &[Duration], percent: f64) -> Duration {
+  let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+  sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Result of the benchmark of the single result node of the test case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+  /// Name of the test file, including path.
+  pub file_name: String,
+  /// Identifier of the test case.
+  pub test_id: String,
+  /// Name of the result node.
+  pub result_name: String,
+  /// Labels of the test file.
+  pub labels: Vec<String>,
+  /// Latencies of successful measured evaluations, returning the expected value.
+  pub samples: Vec<Duration>,
+  /// Number of failed measured evaluations, including evaluations returning unexpected values.
+  pub errors: usize,
+  /// Time elapsed during all measured evaluations.
+  pub elapsed: Duration,
+}
+
+impl BenchmarkResult {
+  /// Returns latency statistics of successful evaluations.
+  pub fn stats(&self) -> Option<LatencyStats> {
+    LatencyStats::from_samples(&self.samples)
+  }
+
+  /// Returns the number of evaluations per second, including failed evaluations.
+  pub fn throughput(&self) -> f64 {
+    let count = self.samples.len() + self.errors;
+    if self.elapsed.is_zero() {
+      0.0
+    } else {
+      count as f64 / self.elapsed.as_secs_f64()
+    }
+  }
+}
+
+/// Options of the benchmark.
+#[derive(Debug, Clone)]
+pub struct BenchmarkOptions {
+  /// Parameters of the benchmark.
+  pub params: BenchmarkParams,
+  /// Options for comparing actual and expected values.
+  pub compare_options: CompareOptions,
+}
+
+/// Benchmarks all tests from the test file and reports results to the listener.
+/// Every result node is evaluated `warmup_iterations` times without measuring and then
+/// `iterations` times with measuring the latency of every evaluation. Evaluations are spread
+/// evenly between specified engines, each engine sends evaluations from a separate thread.
+/// Evaluations returning values different from expected values are counted as errors.
+/// Tests of models that failed to deploy are not benchmarked.
+/// Remaining tests are not benchmarked when the run is cancelled.
+pub fn benchmark_tests(
+  test_file: &TestFile,
+  root_dir: &Path,
+  engines: &mut [&mut dyn Engine],
+  options: &BenchmarkOptions,
+  deployments: &Deployments,
+  listener: &mut dyn Listener,
+  cancellation: &CancellationToken,
+) -> Result<()> {
+  let file_name = test_file.file_name.as_str();
+  let test_cases = &test_file.test_cases;
+  listener.on_event(&Event::FileStarted(file_name.to_string()))?;
+  let tag = test_cases
+    .model_name
+    .as_ref()
+    .map(|model_name| model_tag(root_dir, &dir_name(file_name), model_name));
+  if tag.as_ref().is_some_and(|tag| deployments.errors.contains_key(tag)) {
+    return listener.on_event(&Event::FileFinished(file_name.to_string()));
+  }
+  'test_cases: for (index, test_case) in test_cases.test_cases.iter().enumerate() {
+    for result_node in &test_case.result_nodes {
+      if cancellation.is_cancelled() {
+        break 'test_cases;
+      }
+      let params = evaluate_params(&tag, test_case, result_node);
+      let pending = pending_outcome(file_name, index, test_case, result_node, &params);
+      for engine in engines.iter_mut() {
+        engine.set_test(&relative_path(root_dir, file_name), &pending.test_id, &result_node.name);
+      }
+      let measurement = Measurement {
+        params: &params,
+        result_node,
+        pending: &pending,
+        compare_options: &options.compare_options,
+      };
+      measure(engines, &measurement, options.params.warmup_iterations);
+      let (samples, errors, elapsed) = measure(engines, &measurement, options.params.iterations);
+      let result = BenchmarkResult {
+        file_name: file_name.to_string(),
+        test_id: pending.test_id.clone(),
+        result_name: result_node.name.clone(),
+        labels: test_cases.labels.clone(),
+        samples,
+        errors,
+        elapsed,
+      };
+      listener.on_event(&Event::TestBenchmarked(Box::new(result)))?;
+    }
+  }
+  listener.on_event(&Event::FileFinished(file_name.to_string()))
+}
+
+/// Evaluation of the result node measured in the benchmark.
+struct Measurement<'a> {
+  /// Parameters of the evaluation.
+  params: &'a EvaluateParams,
+  /// Evaluated result node.
+  result_node: &'a ResultNode,
+  /// Outcome of the result node before evaluation, with the expected value.
+  pending: &'a TestOutcome,
+  /// Options for comparing actual and expected values.
+  compare_options: &'a CompareOptions,
+}
+
+impl Measurement<'_> {
+  /// Evaluates the result node, returns the latency when the engine returned the expected value.
+  fn evaluate(&self, engine: &mut dyn Engine) -> Option<Duration> {
+    let started = Instant::now();
+    let evaluated = engine.evaluate(self.params);
+    let latency = started.elapsed();
+    let mut outcome = self.pending.clone();
+    check_result(evaluated, self.result_node, self.compare_options, &mut outcome);
+    (outcome.status == TestStatus::Success).then_some(latency)
+  }
+}
+
+/// Evaluates the result node specified number of times using all engines concurrently.
+/// Returns latencies of successful evaluations, the number of failed evaluations and the elapsed time.
+fn measure(
+  engines: &mut [&mut dyn Engine],
+  measurement: &Measurement,
+  iterations: usize,
+) -> (Vec<Duration>, usize, Duration) {
+  let workers = engines.len();
+  let started = Instant::now();
+  let measurements: Vec<(Vec<Duration>, usize)> = thread::scope(|scope| {
+    let handles: Vec<_> = engines
+      .iter_mut()
+      .enumerate()
+      .map(|(index, engine)| {
+        let count = iterations / workers + usize::from(index < iterations % workers);
+        scope.spawn(move || {
+          let mut samples = vec![];
+          let mut errors = 0;
+          for _ in 0..count {
+            match measurement.evaluate(&mut **engine) {
+              Some(latency) => samples.push(latency),
+              None => errors += 1,
+            }
+          }
+          (samples, errors)
+        })
+      })
+      .collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+  });
+  let elapsed = started.elapsed();
+  let mut samples = vec![];
+  let mut errors = 0;
+  for (worker_samples, worker_errors) in measurements {
+    samples.extend(worker_samples);
+    errors += worker_errors;
+  }
+  (samples, errors, elapsed)
+}
+
+/// Statistics written in the benchmark report, latencies are in milliseconds.
+#[derive(Serialize)]
+struct StatsRow {
+  #[serde(rename = "count")]
+  count: usize,
+  #[serde(rename = "errors")]
+  errors: usize,
+  #[serde(rename = "minMs")]
+  min_ms: Option<f64>,
+  #[serde(rename = "medianMs")]
+  median_ms: Option<f64>,
+  #[serde(rename = "p95Ms")]
+  p95_ms: Option<f64>,
+  #[serde(rename = "p99Ms")]
+  p99_ms: Option<f64>,
+  #[serde(rename = "maxMs")]
+  max_ms: Option<f64>,
+  #[serde(rename = "throughput")]
+  throughput: f64,
+}
+
+impl From<&BenchmarkResult> for StatsRow {
+  fn from(result: &BenchmarkResult) -> Self {
+    let stats = result.stats();
+    let ms = |latency: fn(&LatencyStats) -> Duration| stats.as_ref().map(|stats| millis(latency(stats)));
+    Self {
+      count: result.samples.len(),
+      errors: result.errors,
+      min_ms: ms(|stats| stats.min),
+      median_ms: ms(|stats| stats.median),
+      p95_ms: ms(|stats| stats.p95),
+      p99_ms: ms(|stats| stats.p99),
+      max_ms: ms(|stats| stats.max),
+      throughput: result.throughput(),
+    }
+  }
+}
+
+/// Statistics of the single result node written in the benchmark report.
+#[derive(Serialize)]
+struct TestRow {
+  #[serde(rename = "dir")]
+  dir: String,
+  #[serde(rename = "file")]
+  file: String,
+  #[serde(rename = "id")]
+  id: String,
+  #[serde(rename = "result")]
+  result: String,
+  #[serde(rename = "labels")]
+  labels: Vec<String>,
+  #[serde(flatten)]
+  stats: StatsRow,
+}
+
+/// Statistics of all result nodes with the same label written in the benchmark report.
+#[derive(Serialize)]
+struct LabelRow {
+  #[serde(rename = "label")]
+  label: String,
+  #[serde(flatten)]
+  stats: StatsRow,
+}
+
+/// Benchmark report in JSON format.
+#[derive(Serialize)]
+struct BenchmarkDocument {
+  #[serde(rename = "tests")]
+  tests: Vec<TestRow>,
+  #[serde(rename = "labels")]
+  labels: Vec<LabelRow>,
+}
+
+/// Listener collecting benchmark results, the report with statistics of every result node
+/// and every label is written when the run finishes.
+pub struct BenchmarkReport {
+  writer: BufWriter<File>,
+  format: BenchmarkFormat,
+  results: Vec<BenchmarkResult>,
+}
+
+impl BenchmarkReport {
+  /// Creates the report file.
+  pub fn new(file_path: &str, format: BenchmarkFormat) -> Result<Self> {
+    Ok(Self {
+      writer: BufWriter::new(File::create(file_path)?),
+      format,
+      results: vec![],
+    })
+  }
+
+  /// Returns results of all result nodes with the same label, indexed by label.
+  /// Samples of result nodes are merged, errors and elapsed times are summed up.
+  pub fn label_results(results: &[BenchmarkResult]) -> BTreeMap<String, BenchmarkResult> {
+    let mut label_results: BTreeMap<String, BenchmarkResult> = BTreeMap::new();
+    for result in results {
+      for label in &result.labels {
+        let label_result = label_results.entry(label.clone()).or_insert_with(|| BenchmarkResult {
+          file_name: String::new(),
+          test_id: String::new(),
+          result_name: String::new(),
+          labels: vec![label.clone()],
+          samples: vec![],
+          errors: 0,
+          elapsed: Duration::default(),
+        });
+        label_result.samples.extend(&result.samples);
+        label_result.errors += result.errors;
+        label_result.elapsed += result.elapsed;
+      }
+    }
+    label_results
+  }
+
+  /// Writes the report with collected results.
+  fn write(&mut self) -> Result<()> {
+    let document = BenchmarkDocument {
+      tests: self
+        .results
+        .iter()
+        .map(|result| TestRow {
+          dir: dir_name(&result.file_name),
+          file: file_name(&result.file_name),
+          id: result.test_id.clone(),
+          result: result.result_name.clone(),
+          labels: result.labels.clone(),
+          stats: StatsRow::from(result),
+        })
+        .collect(),
+      labels: Self::label_results(&self.results)
+        .iter()
+        .map(|(label, result)| LabelRow {
+          label: label.clone(),
+          stats: StatsRow::from(result),
+        })
+        .collect(),
+    };
+    match self.format {
+      BenchmarkFormat::Csv => {
+        writeln!(
+          self.writer,
+          r#""scope","dir","file","id","result","label","count","errors","min_ms","median_ms","p95_ms","p99_ms","max_ms","throughput""#
+        )?;
+        for row in &document.tests {
+          let label = row.labels.join(";");
+          let columns = ["test", &row.dir, &row.file, &row.id, &row.result, &label];
+          self.write_csv_line(&columns, &row.stats)?;
+        }
+        for row in &document.labels {
+          self.write_csv_line(&["label", "", "", "", "", &row.label], &row.stats)?;
+        }
+      }
+      BenchmarkFormat::Json => {
+        serde_json::to_writer_pretty(&mut self.writer, &document)
+          .map_err(|reason| RunnerError::SerializingFailed(reason.to_string()))?;
+        writeln!(self.writer)?;
+      }
+    }
+    Ok(self.writer.flush()?)
+  }
+
+  /// Writes a single line of the report in CSV format.
+  fn write_csv_line(&mut self, columns: &[&str], stats: &StatsRow) -> Result<()> {
+    let ms = |value: Option<f64>| value.map(|value| format!("{:.3}", value)).unwrap_or_default();
+    let values = [
+      stats.count.to_string(),
+      stats.errors.to_string(),
+      ms(stats.min_ms),
+      ms(stats.median_ms),
+      ms(stats.p95_ms),
+      ms(stats.p99_ms),
+      ms(stats.max_ms),
+      format!("{:.1}", stats.throughput),
+    ];
+    let line: Vec<String> = columns
+      .iter()
+      .map(|column| column.to_string())
+      .chain(values)
+      .map(|column| quote(&column))
+      .collect();
+    writeln!(self.writer, "{}", line.join(","))?;
+    Ok(())
+  }
+}
+
+impl Listener for BenchmarkReport {
+  fn on_event(&mut self, event: &Event) -> Result<()> {
+    match event {
+      Event::TestBenchmarked(result) => self.results.push(result.as_ref().clone()),
+      Event::RunFinished(_) => self.write()?,
+      _ => {}
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,142 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Checkpoint with outcomes of completed tests, used to resume an interrupted run.
+//!
+//! Outcomes are appended to the checkpoint file as the run proceeds, one JSON document per line,
+//! so the checkpoint remains readable even when the runner is terminated abruptly.
+
+use crate::errors::{Result, RunnerError};
+use crate::runner::events::{Event, Listener, TestOutcome, TestStatus};
+use crate::runner::{test_id, TestFile};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+
+/// Key identifying the test, built from the test file name, test identifier and result node name.
+type TestKey = (String, String, String);
+
+/// Returns the key identifying the test with specified outcome.
+fn test_key(outcome: &TestOutcome) -> TestKey {
+  (
+    outcome.file_name.clone(),
+    outcome.test_id.clone(),
+    outcome.result_name.clone(),
+  )
+}
+
+/// Outcomes of tests completed in the interrupted run.
+///
+/// Skipped tests and tests that failed because the engine could not be reached
+/// are not considered completed, they are executed again when the run is resumed.
+#[derive(Debug, Default)]
+pub struct Checkpoint {
+  outcomes: BTreeMap<TestKey, TestOutcome>,
+}
+
+impl Checkpoint {
+  /// Loads outcomes from the checkpoint file, lines that can not be parsed
+  /// (like the last line written partially when the run was interrupted) are ignored.
+  pub fn load(file_path: &str) -> Result<Self> {
+    let content = fs::read_to_string(file_path)
+      .map_err(|reason| RunnerError::ReadingFileFailed(format!("{}: {}", file_path, reason)))?;
+    let outcomes = content
+      .lines()
+      .filter_map(|line| serde_json::from_str::<TestOutcome>(line).ok())
+      .filter(|outcome| outcome.status != TestStatus::Skipped && !outcome.transport_failed)
+      .map(|outcome| (test_key(&outcome), outcome))
+      .collect();
+    Ok(Self { outcomes })
+  }
+
+  /// Returns the outcome of the completed test.
+  pub fn outcome(&self, file_name: &str, test_id: &str, result_name: &str) -> Option<&TestOutcome> {
+    self
+      .outcomes
+      .get(&(file_name.to_string(), test_id.to_string(), result_name.to_string()))
+  }
+
+  /// Returns `true` when all tests from the test file are completed.
+  pub fn is_completed(&self, test_file: &TestFile) -> bool {
+    test_file
+      .test_cases
+      .test_cases
+      .iter()
+      .enumerate()
+      .all(|(index, test_case)| {
+        test_case.result_nodes.iter().all(|result_node| {
+          self
+            .outcome(&test_file.file_name, &test_id(test_case, index), &result_node.name)
+            .is_some()
+        })
+      })
+  }
+
+  /// Returns the number of completed tests.
+  pub fn len(&self) -> usize {
+    self.outcomes.len()
+  }
+
+  /// Returns `true` when no test was completed.
+  pub fn is_empty(&self) -> bool {
+    self.outcomes.is_empty()
+  }
+}
+
+/// Listener appending outcomes of evaluated tests to the checkpoint file,
+/// every outcome is flushed immediately. Outcomes already present in the checkpoint are not written again.
+pub struct CheckpointWriter {
+  file: File,
+  written: BTreeSet<TestKey>,
+}
+
+impl CheckpointWriter {
+  /// Creates the new checkpoint file, replacing the existing one.
+  pub fn create(file_path: &str) -> Result<Self> {
+    Ok(Self {
+      file: File::create(file_path)?,
+      written: BTreeSet::new(),
+    })
+  }
+
+  /// Opens the existing checkpoint file for appending outcomes of tests not completed yet,
+  /// the line written partially when the run was interrupted is terminated first.
+  pub fn append(file_path: &str, checkpoint: &Checkpoint) -> Result<Self> {
+    let terminated = fs::read(file_path).map_or(true, |content| content.is_empty() || content.ends_with(b"\n"));
+    let mut file = OpenOptions::new().create(true).append(true).open(file_path)?;
+    if !terminated {
+      writeln!(file)?;
+    }
+    Ok(Self {
+      file,
+      written: checkpoint.outcomes.keys().cloned().collect(),
+    })
+  }
+}
+
+impl Listener for CheckpointWriter {
+  fn on_event(&mut self, event: &Event) -> Result<()> {
+    if let Event::TestEvaluated(outcome) = event {
+      if self.written.insert(test_key(outcome)) {
+        let line = serde_json::to_string(outcome.as_ref())
+          .map_err(|reason| RunnerError::SerializingFailed(reason.to_string()))?;
+        writeln!(self.file, "{}", line)?;
+        self.file.flush()?;
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,147 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Listener printing the progress of the run to the console.
+
+use crate::errors::Result;
+use crate::runner::events::{Event, Listener, TestStatus};
+use crate::runner::{file_name, millis};
+
+/// Listener printing events to standard output, failures are printed to standard error.
+#[derive(Debug, Default)]
+pub struct ConsoleListener;
+
+impl Listener for ConsoleListener {
+  fn on_event(&mut self, event: &Event) -> Result<()> {
+    match event {
+      Event::RunStarted(dir) => {
+        println!("Starting DMN TCK runner...");
+        println!("Searching DMN files in directory: {}", dir);
+      }
+      Event::EngineUnavailable(reason) => eprintln!("\nENGINE UNAVAILABLE: {}", reason),
+      Event::EngineAvailable => println!("\nEngine available"),
+      Event::DeploymentCacheInvalid(reason) => {
+        eprintln!("\nInvalid deployment cache, starting with empty cache: {}", reason)
+      }
+      Event::ModelDeployed(tag, id, duration) => println!(
+        "\nDeployed: {}\n      id: {}\n    time: {:.3} ms",
+        tag,
+        id.as_deref().unwrap_or("(no value)"),
+        millis(*duration)
+      ),
+      Event::ModelUnchanged(tag) => println!("\nUnchanged: {}", tag),
+      Event::ModelDeploymentFailed(tag, reason) => eprintln!("\nDEPLOYMENT FAILED: {}: {}", tag, reason),
+      Event::ModelUndeployed(tag) => println!("\nUndeployed: {}", tag),
+      Event::ModelUndeploymentFailed(tag, reason) => eprintln!("\nUNDEPLOYMENT FAILED: {}: {}", tag, reason),
+      Event::FileStarted(file_name) => println!("\nProcessing file: {}", file_name),
+      Event::TestEvaluated(outcome) => {
+        println!(
+          "Executing test case: {}, result name: '{}', artifact: '{}', invoked: '{}'",
+          outcome.test_id, outcome.result_name, outcome.artifact, outcome.invoked
+        );
+        if let Some(duration) = outcome.duration {
+          println!("Evaluated in {:.3} ms", millis(duration));
+        }
+        match outcome.status {
+          TestStatus::Success => println!("SUCCESS"),
+          TestStatus::Failure => {
+            if let (Some(actual), Some(expected)) = (&outcome.actual, &outcome.expected) {
+              eprintln!("  actual: {:?}", actual);
+              eprintln!("expected: {:?}", expected);
+            }
+            eprintln!("FAILURE: {}", outcome.remarks);
+          }
+          status => println!("{}: {}", status, outcome.remarks),
+        }
+      }
+      Event::TestBenchmarked(result) => {
+        println!(
+          "Benchmarked test case: {}, result name: '{}'",
+          result.test_id, result.result_name
+        );
+        if let Some(stats) = result.stats() {
+          println!(
+            "  min: {:.3} ms, median: {:.3} ms, p95: {:.3} ms, p99: {:.3} ms, max: {:.3} ms, throughput: {:.1}/s",
+            millis(stats.min),
+            millis(stats.median),
+            millis(stats.p95),
+            millis(stats.p99),
+            millis(stats.max),
+            result.throughput()
+          );
+        }
+        if result.errors > 0 {
+          eprintln!("  FAILED EVALUATIONS: {}", result.errors);
+        }
+      }
+      Event::FileFinished(_) => {}
+      Event::RunAborted(reason) => eprintln!("\nRUN ABORTED: {}", reason),
+      Event::RunFinished(summary) => {
+        if let Some(reason) = &summary.stop_reason {
+          println!("\nRun stopped, {}, remaining tests were not executed.", reason);
+        } else if summary.cancelled {
+          println!("\nRun cancelled, remaining tests were not executed.");
+        }
+        println!("\nDeployed {} *.dmn files.", summary.deployed_models);
+        if !summary.deployment_errors.is_empty() {
+          println!("Deployment failed for {} *.dmn files:", summary.deployment_errors.len());
+          for (tag, reason) in &summary.deployment_errors {
+            println!("  {}: {}", tag, reason);
+          }
+        }
+        println!("Processed {} test files.", summary.processed_files);
+        let total_count = summary.total_count();
+        let perc = |count: u64| {
+          if total_count > 0 {
+            (count * 100) as f64 / total_count as f64
+          } else {
+            0.
+          }
+        };
+        println!("-----------------");
+        println!("    Total: {:>4}", total_count);
+        println!(
+          "  Success: {:>4}{:>6.1}%",
+          summary.success_count,
+          perc(summary.success_count)
+        );
+        println!(
+          "  Failure: {:>4}{:>6.1}%",
+          summary.failure_count,
+          perc(summary.failure_count)
+        );
+        println!(
+          "    Other: {:>4}{:>6.1}%",
+          summary.other_count,
+          perc(summary.other_count)
+        );
+        if !summary.slowest_tests.is_empty() {
+          println!("\nSlowest tests:");
+          for timing in &summary.slowest_tests {
+            println!(
+              "  {:>10.3} ms  {}  {}  '{}'",
+              millis(timing.duration),
+              file_name(&timing.file_name),
+              timing.test_id,
+              timing.result_name
+            );
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+}
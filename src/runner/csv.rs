@@ -0,0 +1,124 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Listeners writing test results and deployment times to reports in CSV format.
+
+use crate::errors::Result;
+use crate::runner::events::{Event, Listener};
+use crate::runner::{dir_name, file_name, millis};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Listener writing a line with directory, file name, test identifier, status, remarks
+/// and evaluation time in milliseconds for every evaluated test, the report is flushed when the run finishes.
+pub struct CsvReport {
+  writer: BufWriter<File>,
+}
+
+impl CsvReport {
+  /// Creates the report file.
+  pub fn new(file_path: &str) -> Result<Self> {
+    Ok(Self {
+      writer: BufWriter::new(File::create(file_path)?),
+    })
+  }
+}
+
+impl Listener for CsvReport {
+  fn on_event(&mut self, event: &Event) -> Result<()> {
+    match event {
+      Event::TestEvaluated(outcome) => write_line(
+        &mut self.writer,
+        &[
+          &dir_name(&outcome.file_name),
+          &file_name(&outcome.file_name),
+          &outcome.test_id,
+          &outcome.status.to_string(),
+          &outcome.remarks,
+          &format_millis(outcome.duration),
+        ],
+      )?,
+      Event::FileFinished(_) | Event::RunFinished(_) => self.writer.flush()?,
+      _ => {}
+    }
+    Ok(())
+  }
+}
+
+/// Listener writing a line with directory, file name, identifier returned by the engine
+/// and deployment time in milliseconds for every deployed model, the report is flushed when the run finishes.
+/// Directories have the same form as in the report of test results.
+pub struct DeploymentReport {
+  writer: BufWriter<File>,
+  root_dir: PathBuf,
+}
+
+impl DeploymentReport {
+  /// Creates the report file.
+  pub fn new(file_path: &str) -> Result<Self> {
+    Ok(Self {
+      writer: BufWriter::new(File::create(file_path)?),
+      root_dir: PathBuf::new(),
+    })
+  }
+}
+
+impl Listener for DeploymentReport {
+  fn on_event(&mut self, event: &Event) -> Result<()> {
+    match event {
+      Event::RunStarted(dir) => {
+        let dir = PathBuf::from(dir);
+        self.root_dir = dir.canonicalize().unwrap_or(dir);
+      }
+      Event::ModelDeployed(tag, id, duration) => {
+        let file_path = self.root_dir.join(tag).display().to_string();
+        write_line(
+          &mut self.writer,
+          &[
+            &dir_name(&file_path),
+            &file_name(&file_path),
+            id.as_deref().unwrap_or_default(),
+            &format_millis(Some(*duration)),
+          ],
+        )?
+      }
+      Event::RunFinished(_) => self.writer.flush()?,
+      _ => {}
+    }
+    Ok(())
+  }
+}
+
+/// Writes a single line of the report, every value is quoted.
+fn write_line(writer: &mut impl Write, values: &[&str]) -> Result<()> {
+  let line: Vec<String> = values.iter().map(|value| quote(value)).collect();
+  writeln!(writer, "{}", line.join(","))?;
+  Ok(())
+}
+
+/// Quotes the value written in CSV format, quotes inside the value are doubled.
+pub fn quote(value: &str) -> String {
+  format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// Formats the duration in milliseconds.
+fn format_millis(duration: Option<Duration>) -> String {
+  duration
+    .map(|duration| format!("{:.3}", millis(duration)))
+    .unwrap_or_default()
+}
@@ -0,0 +1,175 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Events emitted by the runner and listeners receiving them.
+
+use crate::dto::ValueDto;
+use crate::errors::Result;
+use crate::runner::benchmark::BenchmarkResult;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Status of the evaluated test.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TestStatus {
+  /// Actual value is equal to expected value.
+  #[serde(rename = "SUCCESS")]
+  Success,
+  /// Actual value differs from expected value, or evaluation failed.
+  #[serde(rename = "FAILURE")]
+  Failure,
+  /// Values could not be prepared for comparison.
+  #[serde(rename = "ERROR")]
+  Error,
+  /// Test was not evaluated.
+  #[serde(rename = "SKIPPED")]
+  Skipped,
+}
+
+impl fmt::Display for TestStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TestStatus::Success => write!(f, "SUCCESS"),
+      TestStatus::Failure => write!(f, "FAILURE"),
+      TestStatus::Error => write!(f, "ERROR"),
+      TestStatus::Skipped => write!(f, "SKIPPED"),
+    }
+  }
+}
+
+/// Outcome of the single result node of the test case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestOutcome {
+  /// Name of the test file, including path.
+  #[serde(rename = "fileName")]
+  pub file_name: String,
+  /// Identifier of the test case.
+  #[serde(rename = "testId")]
+  pub test_id: String,
+  /// Name of the result node.
+  #[serde(rename = "resultName")]
+  pub result_name: String,
+  /// Type of evaluated decision artifact.
+  #[serde(rename = "artifact")]
+  pub artifact: String,
+  /// Name of the invoked decision artifact.
+  #[serde(rename = "invoked")]
+  pub invoked: String,
+  /// Status of the test.
+  #[serde(rename = "status")]
+  pub status: TestStatus,
+  /// Remarks explaining the status.
+  #[serde(rename = "remarks")]
+  pub remarks: String,
+  /// Flag indicating if the test failed because the engine could not be reached.
+  #[serde(rename = "transportFailed", default)]
+  pub transport_failed: bool,
+  /// Time of evaluating the result node, not present when the test was not evaluated.
+  #[serde(rename = "duration", default)]
+  pub duration: Option<Duration>,
+  /// Actual value, when returned by the engine.
+  #[serde(rename = "actual", default)]
+  pub actual: Option<ValueDto>,
+  /// Expected value, when present in test case.
+  #[serde(rename = "expected", default)]
+  pub expected: Option<ValueDto>,
+}
+
+/// Summary of the whole run.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RunSummary {
+  /// Number of deployed models, including unchanged models.
+  pub deployed_models: usize,
+  /// Reasons of failed deployments, indexed by model tags.
+  pub deployment_errors: BTreeMap<String, String>,
+  /// Number of processed test files.
+  pub processed_files: usize,
+  /// Number of successful tests.
+  pub success_count: u64,
+  /// Number of failed tests.
+  pub failure_count: u64,
+  /// Number of tests with other status.
+  pub other_count: u64,
+  /// Flag indicating if the run was cancelled before all tests were executed.
+  pub cancelled: bool,
+  /// Reason of stopping the run, when stopped by fail-fast condition.
+  pub stop_reason: Option<String>,
+  /// Slowest evaluated tests, sorted from the slowest one.
+  pub slowest_tests: Vec<TestTiming>,
+}
+
+/// Time of evaluating the result node of the test case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestTiming {
+  /// Name of the test file, including path.
+  pub file_name: String,
+  /// Identifier of the test case.
+  pub test_id: String,
+  /// Name of the result node.
+  pub result_name: String,
+  /// Time of evaluating the result node.
+  pub duration: Duration,
+}
+
+impl RunSummary {
+  /// Returns the total number of tests.
+  pub fn total_count(&self) -> u64 {
+    self.success_count + self.failure_count + self.other_count
+  }
+}
+
+/// Events emitted by the runner.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+  /// Run started for the test cases directory.
+  RunStarted(String),
+  /// Engine did not pass the health check, the reason is attached.
+  EngineUnavailable(String),
+  /// Engine passed the health check after being unavailable.
+  EngineAvailable,
+  /// Deployment cache could not be loaded, the run starts with empty cache. The reason is attached.
+  DeploymentCacheInvalid(String),
+  /// Model with specified tag was deployed, the identifier returned by the engine
+  /// and the time of deployment are attached.
+  ModelDeployed(String, Option<String>, Duration),
+  /// Model with specified tag was not changed since the previous deployment.
+  ModelUnchanged(String),
+  /// Deployment of the model with specified tag failed.
+  ModelDeploymentFailed(String, String),
+  /// Model with specified tag was removed.
+  ModelUndeployed(String),
+  /// Removing the model with specified tag failed.
+  ModelUndeploymentFailed(String, String),
+  /// Processing of the test file started.
+  FileStarted(String),
+  /// Single result node of the test case was evaluated.
+  TestEvaluated(Box<TestOutcome>),
+  /// Single result node of the test case was benchmarked, the test succeeds when no evaluation failed.
+  TestBenchmarked(Box<BenchmarkResult>),
+  /// Processing of the test file finished.
+  FileFinished(String),
+  /// Run was aborted, the reason is attached. Remaining tests are not executed.
+  RunAborted(String),
+  /// Run finished.
+  RunFinished(RunSummary),
+}
+
+/// Listener receiving events emitted by the runner.
+pub trait Listener {
+  /// Handles the event, returned error stops the runner.
+  fn on_event(&mut self, event: &Event) -> Result<()>;
+}
@@ -0,0 +1,1028 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Runner executing test cases against the tested DMN™ engine.
+//!
+//! The [Runner] reports the progress of the run as [events](Event) sent to registered
+//! [listeners](Listener), console output and CSV report are provided as listeners.
+
+use crate::cache::{model_hashes, CachedModel, DeploymentCache};
+use crate::compare::{cast_value, compare_values, CompareOptions};
+use crate::config::{ConfigurationParams, EngineDownAction, FailFastParams};
+use crate::definitions::{
+  deployment_order, invalid_models, model_tag, parse_required_models, required_models, ModelFile,
+};
+use crate::dto::{ActualValueDto, InputNodeDto, ValueDto};
+use crate::encoding::{recover_types, ValueEncoding};
+use crate::engine::{self, Engine};
+use crate::errors::{Result, RunnerError};
+use crate::model::{ResultNode, TestCase, TestCaseType, TestCases};
+use crate::params::{DeployParams, EvaluateParams, UndeployParams, VerifyParams};
+use crate::results::ResultDto;
+use crate::suite::{self, SuiteFormat, SUFFIX_JSON, SUFFIX_XML, SUFFIX_YAML};
+use crate::validator::validate_test_cases_file;
+use http::Uri;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod benchmark;
+mod checkpoint;
+mod console;
+mod csv;
+mod events;
+
+pub use benchmark::{benchmark_tests, BenchmarkOptions, BenchmarkReport, BenchmarkResult, LatencyStats};
+pub use checkpoint::{Checkpoint, CheckpointWriter};
+pub use console::ConsoleListener;
+pub use csv::{CsvReport, DeploymentReport};
+pub use events::{Event, Listener, RunSummary, TestOutcome, TestStatus, TestTiming};
+
+/// Token used for cooperative cancellation of the run. The run stops evaluating tests
+/// as soon as the cancellation is noticed, deployed models are removed, reports
+/// are finalized and the summary is emitted.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+  cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+  /// Requests the cancellation of the run.
+  pub fn cancel(&self) {
+    self.cancelled.store(true, Ordering::SeqCst);
+  }
+
+  /// Returns `true` when the cancellation was requested.
+  pub fn is_cancelled(&self) -> bool {
+    self.cancelled.load(Ordering::SeqCst)
+  }
+}
+
+/// Runner executing test cases from the directory specified in configuration.
+pub struct Runner {
+  /// Configuration of the run.
+  config: ConfigurationParams,
+  /// Tested engine.
+  engine: Box<dyn Engine>,
+  /// Listeners receiving events.
+  listeners: Vec<Box<dyn Listener>>,
+  /// Token for cancelling the run.
+  cancellation: CancellationToken,
+}
+
+impl Runner {
+  /// Creates the runner with the engine specified in configuration.
+  pub fn new(config: ConfigurationParams) -> Result<Self> {
+    let engine = engine::create(&config)?;
+    Ok(Self::with_engine(config, engine))
+  }
+
+  /// Creates the runner testing specified engine.
+  pub fn with_engine(config: ConfigurationParams, engine: Box<dyn Engine>) -> Self {
+    Self {
+      config,
+      engine,
+      listeners: vec![],
+      cancellation: CancellationToken::default(),
+    }
+  }
+
+  /// Registers the listener receiving events emitted by the runner.
+  pub fn add_listener(&mut self, listener: Box<dyn Listener>) {
+    self.listeners.push(listener);
+  }
+
+  /// Returns the token cancelling the run, may be used from other threads.
+  pub fn cancellation_token(&self) -> CancellationToken {
+    self.cancellation.clone()
+  }
+
+  /// Runs all tests from the test cases directory specified in configuration.
+  ///
+  /// Models referenced by selected test files (and models they import) are deployed
+  /// before tests from each directory are evaluated, results are reported to listeners.
+  /// When the checkpoint file is specified in configuration, outcomes of tests are saved
+  /// in the new checkpoint as the run proceeds.
+  pub fn run(&mut self) -> Result<RunSummary> {
+    self.run_tests(None)
+  }
+
+  /// Resumes the interrupted run from the checkpoint file specified in configuration.
+  ///
+  /// Outcomes of tests completed before the interruption are reported to listeners again
+  /// (so the final report contains all tests), remaining tests are executed and saved in the checkpoint.
+  /// Only models required by test files with tests that were not completed are deployed.
+  pub fn resume(&mut self) -> Result<RunSummary> {
+    let checkpoint_file_path = self
+      .config
+      .checkpoint_file_path
+      .clone()
+      .ok_or_else(|| RunnerError::InvalidConfiguration("checkpoint file not specified".to_string()))?;
+    self.run_tests(Some(Checkpoint::load(&checkpoint_file_path)?))
+  }
+
+  /// Runs tests, outcomes of tests completed in specified checkpoint are not evaluated again.
+  fn run_tests(&mut self, checkpoint: Option<Checkpoint>) -> Result<RunSummary> {
+    let checkpoint_writer = match (&self.config.checkpoint_file_path, &checkpoint) {
+      (Some(file_path), Some(checkpoint)) => Some(CheckpointWriter::append(file_path, checkpoint)?),
+      (Some(file_path), None) => Some(CheckpointWriter::create(file_path)?),
+      (None, _) => None,
+    };
+    let options = ExecutionOptions {
+      checkpoint: checkpoint.as_ref(),
+      ..execution_options(&self.config)
+    };
+    self.run_with(
+      checkpoint.as_ref(),
+      checkpoint_writer,
+      |test_file, root_dir, engine, deployments, listener, cancellation| {
+        execute_tests(
+          test_file,
+          root_dir,
+          engine,
+          &options,
+          deployments,
+          listener,
+          cancellation,
+        )
+      },
+    )
+  }
+
+  /// Runs the benchmark of all tests from the test cases directory specified in configuration.
+  ///
+  /// Models are deployed the same way as in [run](Runner::run), every result node is evaluated
+  /// repeatedly and the latency statistics are reported to listeners. Only evaluations returning
+  /// expected values are measured, other evaluations are counted as errors.
+  /// Concurrent requests are sent by additional engines created from configuration,
+  /// so concurrency requires an engine sharing deployed models between instances.
+  pub fn benchmark(&mut self) -> Result<RunSummary> {
+    let params = self
+      .config
+      .benchmark
+      .clone()
+      .ok_or_else(|| RunnerError::InvalidConfiguration("benchmark parameters not specified".to_string()))?;
+    let options = BenchmarkOptions {
+      compare_options: execution_options(&self.config).compare_options,
+      params,
+    };
+    let params = &options.params;
+    if params.concurrency > 1 && self.config.engine_command.is_some() {
+      return Err(RunnerError::InvalidConfiguration(
+        "concurrent benchmark is not supported for engine process".to_string(),
+      ));
+    }
+    let mut workers = (1..params.concurrency)
+      .map(|_| engine::create(&self.config))
+      .collect::<Result<Vec<Box<dyn Engine>>>>()?;
+    self.run_with(
+      None,
+      None,
+      |test_file, root_dir, engine, deployments, listener, cancellation| {
+        let mut engines: Vec<&mut dyn Engine> = vec![&mut *engine];
+        for worker in workers.iter_mut() {
+          engines.push(worker.as_mut());
+        }
+        benchmark_tests(
+          test_file,
+          root_dir,
+          &mut engines,
+          &options,
+          deployments,
+          listener,
+          cancellation,
+        )
+      },
+    )
+  }
+
+  /// Processes selected test files directory by directory with specified function.
+  ///
+  /// Before test files from the directory are processed, models they reference are deployed
+  /// together with imported models (also from other directories) that are not deployed yet.
+  /// Models are removed after the last directory requiring them was processed.
+  /// Models referenced only by test files completed in the checkpoint are not deployed.
+  fn run_with<F>(
+    &mut self,
+    checkpoint: Option<&Checkpoint>,
+    checkpoint_writer: Option<CheckpointWriter>,
+    mut process_file: F,
+  ) -> Result<RunSummary>
+  where
+    F: FnMut(&TestFile, &Path, &mut dyn Engine, &Deployments, &mut dyn Listener, &CancellationToken) -> Result<()>,
+  {
+    let config = &self.config;
+    let listeners = &mut Dispatcher {
+      listeners: &mut self.listeners,
+      summary: RunSummary::default(),
+      fail_fast: fail_fast_params(config),
+      slowest_tests: config.slowest_tests,
+      checkpoint_writer,
+      cancellation: self.cancellation.clone(),
+    };
+    let dir_path = Path::new(&config.test_cases_dir_path);
+    listeners.on_event(&Event::RunStarted(dir_path.display().to_string()))?;
+    let engine = self.engine.as_mut();
+    let cancellation = &self.cancellation;
+    let mut deployments = Deployments::default();
+    let result = (|| -> Result<()> {
+      let ExecutionOptions {
+        ready_timeout,
+        check_interval,
+        ..
+      } = execution_options(config);
+      wait_until_ready(engine, ready_timeout, check_interval, listeners)?;
+      let dmn_files = sorted_files(dir_path, &["dmn"], "")?;
+      let test_files = sorted_files(
+        dir_path,
+        &[SUFFIX_XML, SUFFIX_YAML, SUFFIX_JSON],
+        &config.file_name_pattern,
+      )?;
+      let mut suites: BTreeMap<String, Result<TestFile>> = test_files
+        .iter()
+        .map(|test_file| (test_file.clone(), TestFile::read(test_file)))
+        .collect();
+      let incomplete =
+        |test_file: &&TestFile| checkpoint.map_or(true, |checkpoint| !checkpoint.is_completed(test_file));
+      let referenced = referenced_models(suites.values().flatten().filter(incomplete));
+      let (models, mut failures) = parse_required_models(&dmn_files, &referenced);
+      let invalid = invalid_models(&models);
+      if invalid.is_empty() {
+        failures.retain(|dmn_file, _| referenced.contains(dmn_file));
+      }
+      failures.extend(invalid);
+      let models: Vec<ModelFile> = models
+        .into_iter()
+        .filter(|model| !failures.contains_key(&model.file_name))
+        .collect();
+      let models = deployment_order(&required_models(&models, &referenced)?)?;
+      let mut imported_failures: Vec<(String, String)> = failures
+        .iter()
+        .filter(|(dmn_file, _)| !referenced.contains(*dmn_file))
+        .map(|(dmn_file, reason)| (dmn_file.clone(), reason.clone()))
+        .collect();
+      let root_dir = dir_path.canonicalize()?;
+      let directories: BTreeSet<String> = test_files.iter().map(|test_file| dir_name(test_file)).collect();
+      let mut plan = vec![];
+      for dir in &directories {
+        let dir_test_files: Vec<String> = test_files
+          .iter()
+          .filter(|test_file| dir_name(test_file) == *dir)
+          .cloned()
+          .collect();
+        let dir_referenced = referenced_models(
+          dir_test_files
+            .iter()
+            .filter_map(|test_file| suites.get(test_file))
+            .flatten()
+            .filter(incomplete),
+        );
+        let mut dir_failures: Vec<(String, String)> = vec![];
+        if failures.keys().any(|dmn_file| dir_referenced.contains(dmn_file)) {
+          dir_failures.append(&mut imported_failures);
+        }
+        dir_failures.extend(
+          failures
+            .iter()
+            .filter(|(dmn_file, _)| dir_referenced.contains(*dmn_file))
+            .map(|(dmn_file, reason)| (dmn_file.clone(), reason.clone())),
+        );
+        let dir_models = required_models(&models, &dir_referenced)?;
+        plan.push((dir_test_files, dir_models, dir_failures));
+      }
+      let hashes = model_hashes(&models)?;
+      let mut last_use: BTreeMap<String, usize> = BTreeMap::new();
+      for (index, (_, dir_models, _)) in plan.iter().enumerate() {
+        for model in dir_models {
+          last_use.insert(model.file_name.clone(), index);
+        }
+      }
+      let mut cache = match config
+        .deployment_cache_file_path
+        .as_ref()
+        .map(|p| DeploymentCache::load(p))
+      {
+        Some(Ok(cache)) => cache,
+        Some(Err(reason)) => {
+          listeners.on_event(&Event::DeploymentCacheInvalid(format!("{:?}", reason)))?;
+          DeploymentCache::default()
+        }
+        None => DeploymentCache::default(),
+      };
+      let incremental = config.incremental_deployment && config.deployment_cache_file_path.is_some();
+      let mut attempted: BTreeSet<&str> = BTreeSet::new();
+      let mut failed_namespaces: BTreeMap<&str, String> = BTreeMap::new();
+      for (index, (dir_test_files, dir_models, dir_failures)) in plan.iter().enumerate() {
+        if cancellation.is_cancelled() {
+          break;
+        }
+        for (dmn_file, reason) in dir_failures {
+          if !attempted.insert(dmn_file) {
+            continue;
+          }
+          let tag = model_tag(&root_dir, &dir_name(dmn_file), &file_name(dmn_file));
+          listeners.on_event(&Event::ModelDeploymentFailed(tag.clone(), reason.clone()))?;
+          if !config.continue_on_deployment_failure {
+            return Err(RunnerError::DeploymentFailed(reason.clone()));
+          }
+          deployments.errors.insert(tag, reason.clone());
+        }
+        for model in dir_models {
+          if !attempted.insert(&model.file_name) {
+            continue;
+          }
+          let tag = model_tag(&root_dir, &dir_name(&model.file_name), &file_name(&model.file_name));
+          let hash = hashes.get(&model.file_name).cloned().unwrap_or_default();
+          if incremental {
+            if let Some(cached) = cache.get(&tag, &hash) {
+              if verify_deployment(cached, &tag, engine) {
+                listeners.on_event(&Event::ModelUnchanged(tag.clone()))?;
+                deployments.models.push((tag, model.file_name.clone()));
+                continue;
+              }
+            }
+          }
+          let deployment_started = Instant::now();
+          let deployment = match model.imports.iter().find_map(|i| failed_namespaces.get(i.as_str())) {
+            Some(imported) => Err(RunnerError::DeploymentFailed(format!(
+              "imported model '{}' was not deployed",
+              imported
+            ))),
+            None => deploy_dmn_definitions(&model.file_name, &tag, engine),
+          };
+          match deployment {
+            Ok(id) => {
+              listeners.on_event(&Event::ModelDeployed(
+                tag.clone(),
+                id.clone(),
+                deployment_started.elapsed(),
+              ))?;
+              cache.insert(&tag, &hash, id);
+              deployments.models.push((tag, model.file_name.clone()));
+            }
+            Err(RunnerError::DeploymentFailed(reason)) => {
+              listeners.on_event(&Event::ModelDeploymentFailed(tag.clone(), reason.clone()))?;
+              if !config.continue_on_deployment_failure {
+                return Err(RunnerError::DeploymentFailed(reason));
+              }
+              failed_namespaces.insert(&model.namespace, file_name(&model.file_name));
+              cache.remove(&tag);
+              deployments.errors.insert(tag, reason);
+            }
+            Err(reason) => return Err(reason),
+          }
+        }
+        for test_file_name in dir_test_files {
+          if cancellation.is_cancelled() {
+            break;
+          }
+          let test_file = match suites.remove(test_file_name) {
+            Some(Ok(test_file)) => test_file,
+            Some(Err(reason)) => {
+              listeners.on_event(&Event::FileStarted(test_file_name.clone()))?;
+              return Err(reason);
+            }
+            None => continue,
+          };
+          process_file(&test_file, &root_dir, engine, &deployments, listeners, cancellation)?;
+          listeners.summary.processed_files += 1;
+        }
+        if !incremental {
+          let (released, kept) = deployments
+            .models
+            .drain(..)
+            .partition(|(_, file_name)| last_use.get(file_name).map_or(true, |last_use| *last_use <= index));
+          deployments.models = kept;
+          undeploy_models(&released, engine, &mut cache, listeners)?;
+        }
+        if let Some(cache_file_path) = &config.deployment_cache_file_path {
+          cache.save(cache_file_path)?;
+        }
+      }
+      if !incremental && !deployments.models.is_empty() {
+        undeploy_models(&deployments.models, engine, &mut cache, listeners)?;
+        if let Some(cache_file_path) = &config.deployment_cache_file_path {
+          cache.save(cache_file_path)?;
+        }
+      }
+      Ok(())
+    })();
+    let mut summary = listeners.summary.clone();
+    summary.deployment_errors = deployments.errors;
+    if let Err(reason) = &result {
+      listeners.on_event(&Event::RunAborted(format!("{:?}", reason)))?;
+      summary.stop_reason = listeners.summary.stop_reason.clone();
+    }
+    summary.cancelled = cancellation.is_cancelled();
+    listeners.on_event(&Event::RunFinished(summary.clone()))?;
+    result.map(|_| summary)
+  }
+}
+
+/// Dispatcher sending events to listeners registered in the runner,
+/// the summary of the run is collected from dispatched events.
+struct Dispatcher<'a> {
+  listeners: &'a mut Vec<Box<dyn Listener>>,
+  summary: RunSummary,
+  fail_fast: FailFastParams,
+  slowest_tests: usize,
+  checkpoint_writer: Option<CheckpointWriter>,
+  cancellation: CancellationToken,
+}
+
+impl Listener for Dispatcher<'_> {
+  /// Sends the event to all listeners and the checkpoint, the run is cancelled when any fail-fast condition is met.
+  fn on_event(&mut self, event: &Event) -> Result<()> {
+    if let Some(checkpoint_writer) = &mut self.checkpoint_writer {
+      checkpoint_writer.on_event(event)?;
+    }
+    for listener in self.listeners.iter_mut() {
+      listener.on_event(event)?;
+    }
+    match event {
+      Event::ModelDeployed(_, _, _) | Event::ModelUnchanged(_) => self.summary.deployed_models += 1,
+      Event::TestEvaluated(outcome) => {
+        match outcome.status {
+          TestStatus::Failure => self.summary.failure_count += 1,
+          TestStatus::Success => self.summary.success_count += 1,
+          _ => self.summary.other_count += 1,
+        }
+        if let Some(duration) = outcome.duration {
+          if self.slowest_tests > 0 {
+            let slowest_tests = &mut self.summary.slowest_tests;
+            let index = slowest_tests.partition_point(|timing| timing.duration >= duration);
+            if index < self.slowest_tests {
+              slowest_tests.insert(
+                index,
+                TestTiming {
+                  file_name: outcome.file_name.clone(),
+                  test_id: outcome.test_id.clone(),
+                  result_name: outcome.result_name.clone(),
+                  duration,
+                },
+              );
+              slowest_tests.truncate(self.slowest_tests);
+            }
+          }
+        }
+        if self.summary.stop_reason.is_none() {
+          if let Some(reason) = stop_reason(&self.fail_fast, &self.summary, outcome) {
+            self.summary.stop_reason = Some(reason);
+            self.cancellation.cancel();
+          }
+        }
+      }
+      Event::TestBenchmarked(result) => {
+        if result.errors == 0 {
+          self.summary.success_count += 1;
+        } else {
+          self.summary.failure_count += 1;
+        }
+      }
+      Event::RunAborted(reason) => {
+        if self.summary.stop_reason.is_none() {
+          self.summary.stop_reason = Some(reason.clone());
+        }
+        self.cancellation.cancel();
+      }
+      _ => {}
+    }
+    Ok(())
+  }
+}
+
+/// Checks the engine health until the engine is ready or the timeout elapses,
+/// returns the reason of the last failed health check when the engine is not ready in time.
+/// The first failed health check is reported as [Event::EngineUnavailable],
+/// [Event::EngineAvailable] is reported when the engine becomes ready afterwards.
+pub fn wait_until_ready(
+  engine: &mut dyn Engine,
+  ready_timeout: Duration,
+  check_interval: Duration,
+  listener: &mut dyn Listener,
+) -> Result<()> {
+  let started = Instant::now();
+  let mut unavailable = false;
+  loop {
+    match engine.health() {
+      Ok(()) => {
+        if unavailable {
+          listener.on_event(&Event::EngineAvailable)?;
+        }
+        return Ok(());
+      }
+      Err(reason) => {
+        if !unavailable {
+          listener.on_event(&Event::EngineUnavailable(format!("{:?}", reason)))?;
+          unavailable = true;
+        }
+        let elapsed = started.elapsed();
+        if elapsed >= ready_timeout {
+          return Err(reason);
+        }
+        thread::sleep(check_interval.min(ready_timeout - elapsed));
+      }
+    }
+  }
+}
+
+/// Returns options of executing test files from configuration.
+fn execution_options(config: &ConfigurationParams) -> ExecutionOptions<'static> {
+  ExecutionOptions {
+    compare_options: CompareOptions {
+      ordered_components: config.ordered_components,
+      recover_types: config.value_encoding == ValueEncoding::Plain,
+    },
+    stop_file_on_failure: config.fail_fast.stop_file_on_failure,
+    on_engine_down: config.health.on_engine_down,
+    ready_timeout: Duration::from_millis(config.health.ready_timeout_ms.unwrap_or_default()),
+    check_interval: Duration::from_millis(config.health.check_interval_ms.unwrap_or(1000)),
+    checkpoint: None,
+  }
+}
+
+/// Returns fail-fast conditions from configuration, stopping on failure is the same as stopping after one failure.
+fn fail_fast_params(config: &ConfigurationParams) -> FailFastParams {
+  let mut fail_fast = config.fail_fast.clone();
+  if config.stop_on_failure {
+    fail_fast.max_failures = Some(1);
+  }
+  fail_fast
+}
+
+/// Checks fail-fast conditions after the test was evaluated,
+/// returns the reason of stopping the run when any condition is met.
+pub fn stop_reason(fail_fast: &FailFastParams, summary: &RunSummary, outcome: &TestOutcome) -> Option<String> {
+  if outcome.transport_failed && fail_fast.stop_on_transport_error {
+    return Some(format!("engine not reachable: {}", outcome.remarks));
+  }
+  if let Some(max_failures) = fail_fast.max_failures {
+    if summary.failure_count >= max_failures {
+      return Some(format!("{} failed tests", summary.failure_count));
+    }
+  }
+  if let Some(max_failure_rate) = fail_fast.max_failure_rate {
+    let total_count = summary.total_count();
+    if total_count > 0 && total_count >= fail_fast.min_tests_for_failure_rate {
+      let failure_rate = (summary.failure_count * 100) as f64 / total_count as f64;
+      if failure_rate > max_failure_rate {
+        return Some(format!(
+          "failure rate {:.1}% exceeded {:.1}% after {} tests",
+          failure_rate, max_failure_rate, total_count
+        ));
+      }
+    }
+  }
+  None
+}
+
+/// Returns file names of models referenced by `modelName` in specified test files.
+fn referenced_models<'a>(test_files: impl Iterator<Item = &'a TestFile>) -> BTreeSet<String> {
+  test_files
+    .filter_map(|test_file| {
+      test_file.test_cases.model_name.as_ref().map(|model_name| {
+        Path::new(&dir_name(&test_file.file_name))
+          .join(model_name)
+          .display()
+          .to_string()
+      })
+    })
+    .collect()
+}
+
+/// Deploys definitions from *.dmn file, returns the identifier of the deployed model.
+pub fn deploy_dmn_definitions(dmn_file: &str, tag: &str, engine: &mut dyn Engine) -> Result<Option<String>> {
+  if let Ok(source) = Uri::builder()
+    .scheme("file")
+    .authority("localhost")
+    .path_and_query(dmn_file)
+    .build()
+  {
+    if let Ok(content) = fs::read_to_string(dmn_file) {
+      let params = DeployParams {
+        source: Some(source.to_string()),
+        content: Some(base64::encode(content)),
+        tag: Some(tag.to_string()),
+      };
+      return match engine.deploy(&params) {
+        Ok(result) => {
+          if let Some(data) = result.data {
+            Ok(data.id)
+          } else if result.errors.is_some() {
+            Err(RunnerError::DeploymentFailed(result.errors_as_string()))
+          } else {
+            Err(RunnerError::DeploymentFailed(format!("{:?}", result)))
+          }
+        }
+        Err(reason) => Err(RunnerError::DeploymentFailed(format!("{:?}", reason))),
+      };
+    }
+  }
+  Ok(None)
+}
+
+/// Checks if the model deployed earlier is still available in the engine.
+fn verify_deployment(cached: &CachedModel, tag: &str, engine: &mut dyn Engine) -> bool {
+  let params = VerifyParams {
+    id: cached.id.clone(),
+    tag: Some(tag.to_string()),
+  };
+  engine.verify(&params).unwrap_or(false)
+}
+
+/// Removes deployed definitions, returns `false` when the engine does not support removing models.
+pub fn undeploy_dmn_definitions(tag: &str, engine: &mut dyn Engine) -> Result<bool> {
+  let params = UndeployParams {
+    tag: Some(tag.to_string()),
+  };
+  match engine.undeploy(&params) {
+    Ok(result) => {
+      if result.data.is_some() {
+        Ok(true)
+      } else {
+        Err(RunnerError::UndeploymentFailed(result.errors_as_string()))
+      }
+    }
+    Err(RunnerError::NotSupported(_)) => Ok(false),
+    Err(reason) => Err(reason),
+  }
+}
+
+/// Removes deployed models in the reverse order of their deployment,
+/// removed models are not kept in the deployment cache.
+fn undeploy_models(
+  deployed: &[(String, String)],
+  engine: &mut dyn Engine,
+  cache: &mut DeploymentCache,
+  listener: &mut dyn Listener,
+) -> Result<()> {
+  for (tag, _) in deployed.iter().rev() {
+    match undeploy_dmn_definitions(tag, engine) {
+      Ok(true) => listener.on_event(&Event::ModelUndeployed(tag.clone()))?,
+      Ok(false) => {}
+      Err(reason) => listener.on_event(&Event::ModelUndeploymentFailed(tag.clone(), format!("{:?}", reason)))?,
+    }
+    cache.remove(tag);
+  }
+  Ok(())
+}
+
+/// Deploys again models that may be lost when the engine was down, in the order of their deployment.
+fn redeploy_models(deployed: &[(String, String)], engine: &mut dyn Engine) -> Result<()> {
+  for (tag, file_name) in deployed {
+    deploy_dmn_definitions(file_name, tag, engine)?;
+  }
+  Ok(())
+}
+
+/// Models deployed before test files from the directory are processed.
+#[derive(Debug, Default)]
+pub struct Deployments {
+  /// Tags and file names of deployed models, in the order of deployment.
+  pub models: Vec<(String, String)>,
+  /// Reasons of failed deployments, indexed by model tags.
+  pub errors: BTreeMap<String, String>,
+}
+
+/// Test file with test cases read from it.
+#[derive(Debug)]
+pub struct TestFile {
+  /// Name of the test file.
+  pub file_name: String,
+  /// Test cases read from the file.
+  pub test_cases: TestCases,
+}
+
+impl TestFile {
+  /// Reads test cases from the file, files in XML format are validated first.
+  pub fn read(file_name: &str) -> Result<Self> {
+    if SuiteFormat::from_file_name(file_name)? == SuiteFormat::Xml {
+      validate_test_cases_file(file_name)?;
+    }
+    Ok(Self {
+      file_name: file_name.to_string(),
+      test_cases: suite::read_from_file(file_name)?,
+    })
+  }
+}
+
+/// Options of executing tests from a single test file.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExecutionOptions<'a> {
+  /// Options for comparing actual and expected values.
+  pub compare_options: CompareOptions,
+  /// Flag indicating if remaining tests should be skipped after the first failure.
+  pub stop_file_on_failure: bool,
+  /// Action taken when the engine is down after the evaluation failed on transport.
+  pub on_engine_down: EngineDownAction,
+  /// Maximum time to wait until the engine is ready again when the run is paused.
+  pub ready_timeout: Duration,
+  /// Interval between health checks while waiting for the engine.
+  pub check_interval: Duration,
+  /// Optional checkpoint with outcomes of tests completed in the interrupted run.
+  pub checkpoint: Option<&'a Checkpoint>,
+}
+
+/// Executes all tests from the test file and reports outcomes to the listener.
+/// Outcomes of tests completed in the checkpoint are reported without evaluating tests again.
+/// Tests of models that failed to deploy are reported as skipped, after the first failure
+/// remaining tests are skipped when requested in options.
+/// When the evaluation fails on transport and the engine is down, the run is paused
+/// or aborted as requested in options. When the engine is ready again, deployed models are deployed again
+/// before the evaluation is repeated. Remaining tests are not executed when the run is cancelled.
+pub fn execute_tests(
+  test_file: &TestFile,
+  root_dir: &Path,
+  engine: &mut dyn Engine,
+  options: &ExecutionOptions,
+  deployments: &Deployments,
+  listener: &mut dyn Listener,
+  cancellation: &CancellationToken,
+) -> Result<()> {
+  let file_name = test_file.file_name.as_str();
+  let test_cases = &test_file.test_cases;
+  listener.on_event(&Event::FileStarted(file_name.to_string()))?;
+  let tag = test_cases
+    .model_name
+    .as_ref()
+    .map(|model_name| model_tag(root_dir, &dir_name(file_name), model_name));
+  let deployment_error = tag.as_ref().and_then(|tag| deployments.errors.get(tag));
+  let relative_name = relative_path(root_dir, file_name);
+  let mut file_failed = false;
+  'test_cases: for (index, test_case) in test_cases.test_cases.iter().enumerate() {
+    for result_node in &test_case.result_nodes {
+      if cancellation.is_cancelled() {
+        break 'test_cases;
+      }
+      let params = evaluate_params(&tag, test_case, result_node);
+      let mut outcome = pending_outcome(file_name, index, test_case, result_node, &params);
+      if let Some(completed) = options
+        .checkpoint
+        .and_then(|checkpoint| checkpoint.outcome(file_name, &outcome.test_id, &outcome.result_name))
+      {
+        outcome = completed.clone();
+        file_failed = options.stop_file_on_failure && outcome.status == TestStatus::Failure;
+      } else if let Some(reason) = deployment_error {
+        outcome.status = TestStatus::Skipped;
+        outcome.remarks = format!("deployment failed: {}", reason);
+      } else if file_failed {
+        outcome.status = TestStatus::Skipped;
+        outcome.remarks = "previous test from this file failed".to_string();
+      } else {
+        let pending = outcome.clone();
+        engine.set_test(&relative_name, &outcome.test_id, &outcome.result_name);
+        evaluate(engine, &params, result_node, &options.compare_options, &mut outcome);
+        if outcome.transport_failed && options.on_engine_down != EngineDownAction::Report && engine.health().is_err() {
+          let ready_timeout = match options.on_engine_down {
+            EngineDownAction::Pause => options.ready_timeout,
+            _ => Duration::default(),
+          };
+          if let Err(reason) = wait_until_ready(engine, ready_timeout, options.check_interval, listener) {
+            listener.on_event(&Event::TestEvaluated(Box::new(outcome)))?;
+            listener.on_event(&Event::RunAborted(format!("engine not available: {:?}", reason)))?;
+            break 'test_cases;
+          }
+          if let Err(reason) = redeploy_models(&deployments.models, engine) {
+            listener.on_event(&Event::TestEvaluated(Box::new(outcome)))?;
+            listener.on_event(&Event::RunAborted(format!("redeploying models failed: {:?}", reason)))?;
+            break 'test_cases;
+          }
+          outcome = pending;
+          evaluate(engine, &params, result_node, &options.compare_options, &mut outcome);
+        }
+        file_failed = options.stop_file_on_failure && outcome.status == TestStatus::Failure;
+      }
+      listener.on_event(&Event::TestEvaluated(Box::new(outcome)))?;
+    }
+  }
+  listener.on_event(&Event::FileFinished(file_name.to_string()))
+}
+
+/// Returns the identifier of the test case, test cases without identifiers
+/// are identified by their position in the test file, starting from 1.
+pub fn test_id(test_case: &TestCase, index: usize) -> String {
+  test_case.id.clone().unwrap_or_else(|| format!("#{}", index + 1))
+}
+
+/// Returns the outcome of the result node that was not evaluated yet,
+/// `index` is the position of the test case in the test file.
+pub fn pending_outcome(
+  file_name: &str,
+  index: usize,
+  test_case: &TestCase,
+  result_node: &ResultNode,
+  params: &EvaluateParams,
+) -> TestOutcome {
+  TestOutcome {
+    file_name: file_name.to_string(),
+    test_id: test_id(test_case, index),
+    result_name: result_node.name.clone(),
+    artifact: params.artifact.clone(),
+    invoked: params.name.clone(),
+    status: TestStatus::Failure,
+    remarks: String::new(),
+    transport_failed: false,
+    duration: None,
+    actual: None,
+    expected: result_node.expected.as_ref().map(ValueDto::from),
+  }
+}
+
+/// Evaluates the result node and compares the actual value with the expected value,
+/// the time of evaluation is recorded in the outcome.
+fn evaluate(
+  engine: &mut dyn Engine,
+  params: &EvaluateParams,
+  result_node: &ResultNode,
+  compare_options: &CompareOptions,
+  outcome: &mut TestOutcome,
+) {
+  let started = Instant::now();
+  let evaluated = engine.evaluate(params);
+  outcome.duration = Some(started.elapsed());
+  check_result(evaluated, result_node, compare_options, outcome);
+}
+
+/// Compares the value returned by the engine with the expected value,
+/// the status of the pending outcome is updated with the result of comparison.
+pub fn check_result(
+  evaluated: Result<ResultDto<ActualValueDto>>,
+  result_node: &ResultNode,
+  compare_options: &CompareOptions,
+  outcome: &mut TestOutcome,
+) {
+  match evaluated {
+    Ok(result) => {
+      if let Some(data) = result.data {
+        if let Some(actual_dto) = data.value {
+          if let Some(expected_dto) = outcome.expected.take() {
+            let actual_dto = if compare_options.recover_types {
+              recover_types(&actual_dto, &expected_dto)
+            } else {
+              actual_dto
+            };
+            match cast_values(actual_dto, expected_dto, &result_node.cast) {
+              Ok((actual_dto, expected_dto)) => {
+                if compare_values(&actual_dto, &expected_dto, compare_options) {
+                  outcome.status = TestStatus::Success;
+                } else {
+                  outcome.remarks = "actual <> expected".to_string();
+                }
+                outcome.actual = Some(actual_dto);
+                outcome.expected = Some(expected_dto);
+              }
+              Err(reason) => {
+                outcome.status = TestStatus::Error;
+                outcome.remarks = format!("{:?}", reason);
+              }
+            }
+          } else {
+            outcome.remarks = "no expected value".to_string();
+          }
+        } else {
+          outcome.remarks = "no actual value".to_string();
+        }
+      } else if result.errors.is_some() {
+        outcome.remarks = result.errors_as_string();
+      } else {
+        outcome.remarks = format!("{:?}", result);
+      }
+    }
+    Err(RunnerError::TransportFailed(reason)) => {
+      outcome.remarks = reason;
+      outcome.transport_failed = true;
+    }
+    Err(RunnerError::InvalidResponse(reason)) => {
+      outcome.remarks = reason;
+    }
+    Err(reason) => {
+      outcome.remarks = format!("{:?}", reason);
+    }
+  }
+}
+
+/// Prepares parameters for evaluating the result node of the test case.
+///
+/// Decisions are evaluated by the name of the result node. Business knowledge models
+/// and decision services are invoked by the invocable name of the test case (when present)
+/// with input nodes passed as named parameters, additionally input values are passed
+/// as positional parameters to business knowledge models.
+pub fn evaluate_params(tag: &Option<String>, test_case: &TestCase, result_node: &ResultNode) -> EvaluateParams {
+  let input: Vec<InputNodeDto> = test_case.input_nodes.iter().map(InputNodeDto::from).collect();
+  let (name, result_name, arguments) = match test_case.typ {
+    TestCaseType::Decision => (result_node.name.clone(), None, None),
+    TestCaseType::BusinessKnowledgeModel => (
+      invocable_name(test_case, result_node),
+      Some(result_node.name.clone()),
+      Some(input.iter().map(|input_node| input_node.value.clone()).collect()),
+    ),
+    TestCaseType::DecisionService => (
+      invocable_name(test_case, result_node),
+      Some(result_node.name.clone()),
+      None,
+    ),
+  };
+  EvaluateParams {
+    tag: tag.clone(),
+    artifact: test_case.typ.to_string(),
+    name,
+    input,
+    arguments,
+    result_name,
+    cast: result_node.cast.clone(),
+  }
+}
+
+/// Returns the invocable name of the test case, or the result node name when not specified.
+fn invocable_name(test_case: &TestCase, result_node: &ResultNode) -> String {
+  test_case
+    .invocable_name
+    .clone()
+    .unwrap_or_else(|| result_node.name.clone())
+}
+
+/// Casts actual and expected values when the result node requires casting.
+fn cast_values(actual: ValueDto, expected: ValueDto, cast: &Option<String>) -> Result<(ValueDto, ValueDto)> {
+  if let Some(cast) = cast {
+    Ok((cast_value(&actual, cast)?, cast_value(&expected, cast)?))
+  } else {
+    Ok((actual, expected))
+  }
+}
+
+/// Returns sorted names of files with any of the specified suffixes,
+/// matching the pattern when the pattern is not empty.
+fn sorted_files(path: &Path, suffixes: &[&str], pattern: &str) -> Result<Vec<String>> {
+  let re = if pattern.is_empty() {
+    None
+  } else {
+    Some(Regex::new(pattern).map_err(|reason| {
+      RunnerError::InvalidConfiguration(format!("invalid matching pattern '{}': {}", pattern, reason))
+    })?)
+  };
+  let mut files = search_files(path, suffixes, &re)?;
+  files.sort();
+  Ok(files)
+}
+
+fn search_files(path: &Path, suffixes: &[&str], re: &Option<Regex>) -> Result<Vec<String>> {
+  let mut files = vec![];
+  if let Ok(entries) = fs::read_dir(path) {
+    for entry in entries.flatten() {
+      let path = entry.path();
+      if path.is_dir() {
+        files.append(search_files(&path, suffixes, re)?.as_mut());
+      } else {
+        let file_name = path.canonicalize().unwrap().display().to_string();
+        if suffixes
+          .iter()
+          .any(|suffix| file_name.ends_with(&format!(".{}", suffix)))
+          && re.as_ref().map_or(true, |re| re.is_match(&file_name))
+        {
+          files.push(file_name);
+        }
+      }
+    }
+  }
+  Ok(files)
+}
+
+/// Retrieves the parent path without file name from given `name`.
+pub fn dir_name(name: &str) -> String {
+  Path::new(name).parent().unwrap().to_str().unwrap().to_string()
+}
+
+/// Retrieves the path of the file relative to specified root directory,
+/// files outside the root directory keep their full path.
+pub fn relative_path(root_dir: &Path, name: &str) -> String {
+  let path = Path::new(name);
+  path.strip_prefix(root_dir).unwrap_or(path).display().to_string()
+}
+
+/// Retrieves the file name with extension from given `name`.
+pub fn file_name(name: &str) -> String {
+  Path::new(name).file_name().unwrap().to_str().unwrap().to_string()
+}
+
+/// Returns the duration in milliseconds.
+pub fn millis(duration: Duration) -> f64 {
+  duration.as_secs_f64() * 1000.0
+}
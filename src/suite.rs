@@ -0,0 +1,115 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test case suites stored in XML, JSON or YAML files.
+//!
+//! The format of the file is recognized by its name:
+//! - `*.tck.json` - JSON file with test cases,
+//! - `*.tck.yaml` - YAML file with test cases,
+//! - `*.xml` - XML file with test cases, as defined in TCK.
+
+use crate::dto::TestCasesDto;
+use crate::errors::Result;
+use crate::errors::RunnerError::*;
+use crate::model::{parse_from_file, parse_from_string, TestCases};
+use crate::writer;
+use std::fs;
+
+/// File name suffix of test cases in JSON format.
+pub const SUFFIX_JSON: &str = "tck.json";
+/// File name suffix of test cases in YAML format.
+pub const SUFFIX_YAML: &str = "tck.yaml";
+/// File name suffix of test cases in XML format.
+pub const SUFFIX_XML: &str = "xml";
+
+/// Formats of files containing test cases.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SuiteFormat {
+  /// XML file, as defined in TCK.
+  Xml,
+  /// JSON file.
+  Json,
+  /// YAML file.
+  Yaml,
+}
+
+impl SuiteFormat {
+  /// Recognizes the format of the test cases file by its name.
+  pub fn from_file_name(file_name: &str) -> Result<Self> {
+    if file_name.ends_with(&format!(".{}", SUFFIX_JSON)) {
+      Ok(Self::Json)
+    } else if file_name.ends_with(&format!(".{}", SUFFIX_YAML)) {
+      Ok(Self::Yaml)
+    } else if file_name.ends_with(&format!(".{}", SUFFIX_XML)) {
+      Ok(Self::Xml)
+    } else {
+      Err(UnsupportedFileFormat(file_name.to_string()))
+    }
+  }
+}
+
+/// Reads test cases from file in format recognized by file name.
+pub fn read_from_file(file_name: &str) -> Result<TestCases> {
+  match SuiteFormat::from_file_name(file_name)? {
+    SuiteFormat::Xml => parse_from_file(file_name),
+    format => match fs::read_to_string(file_name) {
+      Ok(content) => read_from_string(&content, format),
+      Err(reason) => Err(ReadingFileFailed(format!("{}", reason))),
+    },
+  }
+}
+
+/// Reads test cases from string in specified format.
+pub fn read_from_string(content: &str, format: SuiteFormat) -> Result<TestCases> {
+  match format {
+    SuiteFormat::Xml => parse_from_string(content),
+    SuiteFormat::Json => serde_json::from_str::<TestCasesDto>(content)
+      .map(|dto| TestCases::from(&dto))
+      .map_err(|reason| ParsingJSONFailed(format!("{}", reason))),
+    SuiteFormat::Yaml => serde_yaml::from_str::<TestCasesDto>(content)
+      .map(|dto| TestCases::from(&dto))
+      .map_err(|reason| ParsingYAMLFailed(format!("{}", reason))),
+  }
+}
+
+/// Writes test cases to file in format recognized by file name.
+pub fn write_to_file(test_cases: &TestCases, file_name: &str) -> Result<()> {
+  match SuiteFormat::from_file_name(file_name)? {
+    SuiteFormat::Xml => writer::write_to_file(test_cases, file_name),
+    format => {
+      fs::write(file_name, write_to_string(test_cases, format)?)?;
+      Ok(())
+    }
+  }
+}
+
+/// Writes test cases to string in specified format.
+pub fn write_to_string(test_cases: &TestCases, format: SuiteFormat) -> Result<String> {
+  match format {
+    SuiteFormat::Xml => Ok(writer::write_to_string(test_cases)),
+    SuiteFormat::Json => serde_json::to_string_pretty(&TestCasesDto::from(test_cases))
+      .map_err(|reason| SerializingFailed(format!("{}", reason))),
+    SuiteFormat::Yaml => {
+      serde_yaml::to_string(&TestCasesDto::from(test_cases)).map_err(|reason| SerializingFailed(format!("{}", reason)))
+    }
+  }
+}
+
+/// Converts test cases between formats recognized by input and output file names.
+pub fn convert(input_file_name: &str, output_file_name: &str) -> Result<()> {
+  let test_cases = read_from_file(input_file_name)?;
+  write_to_file(&test_cases, output_file_name)
+}
@@ -0,0 +1,80 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for benchmark statistics.
+
+use crate::runner::{BenchmarkReport, BenchmarkResult, LatencyStats};
+use std::time::Duration;
+
+fn millis(values: &[u64]) -> Vec<Duration> {
+  values.iter().map(|value| Duration::from_millis(*value)).collect()
+}
+
+fn result(labels: &[&str], samples: &[u64], errors: usize, elapsed_ms: u64) -> BenchmarkResult {
+  BenchmarkResult {
+    file_name: "/tests/test.xml".to_string(),
+    test_id: "001".to_string(),
+    result_name: "result".to_string(),
+    labels: labels.iter().map(|label| label.to_string()).collect(),
+    samples: millis(samples),
+    errors,
+    elapsed: Duration::from_millis(elapsed_ms),
+  }
+}
+
+#[test]
+fn test_latency_stats() {
+  assert_eq!(None, LatencyStats::from_samples(&[]));
+  let samples = millis(&[7, 3, 1, 10, 2, 9, 4, 8, 6, 5]);
+  let stats = LatencyStats::from_samples(&samples).unwrap();
+  assert_eq!(10, stats.count);
+  assert_eq!(Duration::from_millis(1), stats.min);
+  assert_eq!(Duration::from_millis(5), stats.median);
+  assert_eq!(Duration::from_millis(10), stats.p95);
+  assert_eq!(Duration::from_millis(10), stats.p99);
+  assert_eq!(Duration::from_millis(10), stats.max);
+  let samples: Vec<u64> = (1..=200).collect();
+  let stats = LatencyStats::from_samples(&millis(&samples)).unwrap();
+  assert_eq!(Duration::from_millis(100), stats.median);
+  assert_eq!(Duration::from_millis(190), stats.p95);
+  assert_eq!(Duration::from_millis(198), stats.p99);
+  let stats = LatencyStats::from_samples(&millis(&[42])).unwrap();
+  assert_eq!(Duration::from_millis(42), stats.median);
+  assert_eq!(Duration::from_millis(42), stats.p99);
+}
+
+#[test]
+fn test_throughput() {
+  assert_eq!(50.0, result(&[], &[10, 20, 30, 40], 1, 100).throughput());
+  assert_eq!(0.0, result(&[], &[], 0, 0).throughput());
+}
+
+#[test]
+fn test_label_results() {
+  let results = vec![
+    result(&["fast", "all"], &[1, 2], 0, 10),
+    result(&["all"], &[30], 2, 90),
+    result(&[], &[5], 0, 5),
+  ];
+  let label_results = BenchmarkReport::label_results(&results);
+  assert_eq!(vec!["all", "fast"], label_results.keys().collect::<Vec<&String>>());
+  let all = &label_results["all"];
+  assert_eq!(millis(&[1, 2, 30]), all.samples);
+  assert_eq!(2, all.errors);
+  assert_eq!(Duration::from_millis(100), all.elapsed);
+  assert_eq!(Duration::from_millis(2), all.stats().unwrap().median);
+  assert_eq!(millis(&[1, 2]), label_results["fast"].samples);
+}
@@ -0,0 +1,89 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for deployment cache.
+
+use crate::cache::{content_hash, model_hashes, DeploymentCache};
+use crate::definitions::ModelFile;
+
+#[test]
+fn test_content_hash() {
+  assert_eq!("cbf29ce484222325", content_hash(""));
+  assert_eq!("af63dc4c8601ec8c", content_hash("a"));
+  assert_ne!(content_hash("<definitions/>"), content_hash("<definitions />"));
+}
+
+#[test]
+fn test_cached_models() {
+  let mut cache = DeploymentCache::default();
+  cache.insert("dir/a.dmn", "0001", Some("id-a".to_string()));
+  assert_eq!(Some("id-a"), cache.get("dir/a.dmn", "0001").unwrap().id.as_deref());
+  assert!(cache.get("dir/a.dmn", "0002").is_none());
+  assert!(cache.get("dir/b.dmn", "0001").is_none());
+  cache.remove("dir/a.dmn");
+  assert!(cache.get("dir/a.dmn", "0001").is_none());
+}
+
+#[test]
+fn test_save_and_load() {
+  let file_name = std::env::temp_dir()
+    .join(format!("dmn-tck-rs-cache-{}.json", std::process::id()))
+    .display()
+    .to_string();
+  let mut cache = DeploymentCache::default();
+  cache.insert("dir/a.dmn", "0001", Some("id-a".to_string()));
+  cache.insert("dir/b.dmn", "0002", None);
+  cache.save(&file_name).unwrap();
+  assert_eq!(cache, DeploymentCache::load(&file_name).unwrap());
+  std::fs::write(&file_name, "not a cache").unwrap();
+  assert!(DeploymentCache::load(&file_name).is_err());
+  std::fs::remove_file(&file_name).unwrap();
+  assert_eq!(DeploymentCache::default(), DeploymentCache::load(&file_name).unwrap());
+}
+
+#[test]
+fn test_model_hashes_cover_imports() {
+  let dir = std::env::temp_dir().join(format!("dmn-tck-rs-model-hashes-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let file_name = |name: &str| dir.join(name).display().to_string();
+  let models = vec![
+    ModelFile {
+      file_name: file_name("library.dmn"),
+      namespace: "library".to_string(),
+      imports: vec![],
+    },
+    ModelFile {
+      file_name: file_name("model.dmn"),
+      namespace: "model".to_string(),
+      imports: vec!["library".to_string()],
+    },
+  ];
+  std::fs::write(file_name("library.dmn"), "<definitions namespace=\"library\"/>").unwrap();
+  std::fs::write(file_name("model.dmn"), "<definitions namespace=\"model\"/>").unwrap();
+  let hashes = model_hashes(&models).unwrap();
+  std::fs::write(
+    file_name("library.dmn"),
+    "<definitions namespace=\"library\" name=\"changed\"/>",
+  )
+  .unwrap();
+  let changed_hashes = model_hashes(&models).unwrap();
+  std::fs::remove_dir_all(&dir).unwrap();
+  assert_ne!(
+    hashes[&file_name("library.dmn")],
+    changed_hashes[&file_name("library.dmn")]
+  );
+  assert_ne!(hashes[&file_name("model.dmn")], changed_hashes[&file_name("model.dmn")]);
+}
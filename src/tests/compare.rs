@@ -0,0 +1,200 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for comparing values.
+
+use crate::compare::{cast_value, compare_values, CompareOptions};
+use crate::dto::{ComponentDto, ListDto, SimpleDto, ValueDto};
+use crate::errors::RunnerError;
+
+const UNORDERED: CompareOptions = CompareOptions {
+  ordered_components: false,
+  recover_types: false,
+};
+
+const ORDERED: CompareOptions = CompareOptions {
+  ordered_components: true,
+  recover_types: false,
+};
+
+fn simple(text: &str) -> ValueDto {
+  ValueDto {
+    simple: Some(SimpleDto {
+      typ: Some("xsd:string".to_string()),
+      text: Some(text.to_string()),
+      nil: false,
+    }),
+    ..Default::default()
+  }
+}
+
+fn context(components: &[(&str, &str)]) -> ValueDto {
+  ValueDto {
+    components: Some(
+      components
+        .iter()
+        .map(|(name, text)| ComponentDto {
+          name: Some(name.to_string()),
+          value: Some(simple(text)),
+          nil: false,
+        })
+        .collect(),
+    ),
+    ..Default::default()
+  }
+}
+
+fn list(items: Vec<ValueDto>) -> ValueDto {
+  ValueDto {
+    list: Some(ListDto { items, nil: false }),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_simple_values() {
+  assert!(compare_values(&simple("a"), &simple("a"), &UNORDERED));
+  assert!(!compare_values(&simple("a"), &simple("b"), &UNORDERED));
+  assert!(!compare_values(&simple("a"), &list(vec![simple("a")]), &UNORDERED));
+}
+
+#[test]
+fn test_components_order() {
+  let actual = context(&[("b", "2"), ("a", "1")]);
+  let expected = context(&[("a", "1"), ("b", "2")]);
+  assert!(compare_values(&actual, &expected, &UNORDERED));
+  assert!(!compare_values(&actual, &expected, &ORDERED));
+  assert!(compare_values(&expected, &expected, &ORDERED));
+}
+
+#[test]
+fn test_duplicated_components() {
+  let expected = context(&[("a", "1"), ("b", "2"), ("a", "3")]);
+  assert!(compare_values(
+    &context(&[("a", "1"), ("a", "3"), ("b", "2")]),
+    &expected,
+    &UNORDERED
+  ));
+  assert!(!compare_values(
+    &context(&[("a", "3"), ("a", "1"), ("b", "2")]),
+    &expected,
+    &UNORDERED
+  ));
+  assert!(!compare_values(
+    &context(&[("a", "1"), ("b", "2")]),
+    &expected,
+    &UNORDERED
+  ));
+}
+
+#[test]
+fn test_nested_values() {
+  let actual = list(vec![context(&[("y", "2"), ("x", "1")]), simple("c")]);
+  let expected = list(vec![context(&[("x", "1"), ("y", "2")]), simple("c")]);
+  assert!(compare_values(&actual, &expected, &UNORDERED));
+  assert!(!compare_values(&actual, &expected, &ORDERED));
+  let reversed = list(vec![simple("c"), context(&[("x", "1"), ("y", "2")])]);
+  assert!(!compare_values(&reversed, &expected, &UNORDERED));
+}
+
+fn typed(typ: &str, text: &str) -> ValueDto {
+  ValueDto {
+    simple: Some(SimpleDto {
+      typ: Some(typ.to_string()),
+      text: Some(text.to_string()),
+      nil: false,
+    }),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_cast_numbers() {
+  assert_eq!(
+    typed("xsd:decimal", "10.5"),
+    cast_value(&typed("xsd:double", "10.50"), "xsd:decimal").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:decimal", "0.5"),
+    cast_value(&typed("xsd:decimal", "+.500"), "xsd:decimal").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:decimal", "-12"),
+    cast_value(&typed("xsd:decimal", "-012.00"), "xsd:decimal").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:decimal", "0"),
+    cast_value(&typed("xsd:decimal", "-0.0"), "xsd:decimal").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:double", "100"),
+    cast_value(&typed("xsd:double", "1E2"), "xsd:double").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:integer", "7"),
+    cast_value(&typed("xsd:decimal", "7.0"), "xsd:integer").unwrap()
+  );
+  assert_eq!(
+    Err(RunnerError::CastFailed(
+      "value '7.5' can not be casted to 'integer'".to_string()
+    )),
+    cast_value(&typed("xsd:decimal", "7.5"), "xsd:integer")
+  );
+  assert!(cast_value(&typed("xsd:string", "abc"), "xsd:decimal").is_err());
+}
+
+#[test]
+fn test_cast_dates_and_times() {
+  assert_eq!(
+    typed("xsd:date", "2017-01-02"),
+    cast_value(&typed("xsd:dateTime", "2017-01-02T10:11:12Z"), "xsd:date").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:date", "2017-01-02"),
+    cast_value(&typed("xsd:date", "2017-01-02"), "xsd:date").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:time", "10:11:12Z"),
+    cast_value(&typed("xsd:dateTime", "2017-01-02T10:11:12Z"), "xsd:time").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:date", "2017-01-02Z"),
+    cast_value(&typed("xsd:date", "2017-01-02Z"), "xsd:date").unwrap()
+  );
+  assert_eq!(
+    typed("xsd:date", "2017-01-02+02:00"),
+    cast_value(&typed("xsd:date", "2017-01-02+02:00"), "xsd:date").unwrap()
+  );
+  assert!(cast_value(&typed("xsd:date", "2017-01-02+02:00"), "xsd:time").is_err());
+  assert!(cast_value(&typed("xsd:string", "yesterday"), "xsd:date").is_err());
+}
+
+#[test]
+fn test_cast_other_values() {
+  assert_eq!(
+    typed("xsd:boolean", "true"),
+    cast_value(&typed("xsd:string", "1"), "xsd:boolean").unwrap()
+  );
+  assert_eq!(
+    list(vec![typed("xsd:decimal", "1"), typed("xsd:decimal", "2")]),
+    cast_value(
+      &list(vec![typed("xsd:decimal", "1.0"), typed("xsd:decimal", "2.00")]),
+      "xsd:decimal"
+    )
+    .unwrap()
+  );
+  assert!(cast_value(&context(&[("a", "1")]), "xsd:decimal").is_err());
+}
@@ -0,0 +1,42 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for reading configuration files.
+
+use crate::config::load;
+use crate::errors::RunnerError;
+
+#[test]
+fn test_load() {
+  let config = load("runner.yml").unwrap();
+  assert!(!config.test_cases_dir_path.is_empty());
+  let file_name = std::env::temp_dir()
+    .join(format!("dmn-tck-rs-config-{}.yml", std::process::id()))
+    .display()
+    .to_string();
+  assert!(matches!(load(&file_name), Err(RunnerError::ReadingFileFailed(_))));
+  std::fs::write(&file_name, "deploy_url: [").unwrap();
+  let result = load(&file_name);
+  std::fs::remove_file(&file_name).unwrap();
+  match result {
+    Err(reason @ RunnerError::ParsingYAMLFailed(_)) => {
+      assert!(reason
+        .to_string()
+        .starts_with(&format!("parsing YAML failed: configuration file '{}'", file_name)))
+    }
+    other => panic!("unexpected result {:?}", other),
+  }
+}
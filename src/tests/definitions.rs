@@ -0,0 +1,209 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for model definitions and deployment order.
+
+use crate::definitions::{
+  deployment_order, invalid_models, model_tag, parse_model, parse_required_models, required_models, ModelFile,
+};
+use crate::errors::RunnerError;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const IMPORTING: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<definitions xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/"
+             namespace="http://www.trisotech.com/definitions/_importing" name="importing">
+    <import namespace="http://www.trisotech.com/definitions/_imported" name="imported"
+            importType="http://www.omg.org/spec/DMN/20180521/MODEL/"/>
+    <import namespace="http://www.example.com/types" importType="http://www.w3.org/2001/XMLSchema"/>
+</definitions>"#;
+
+fn model(file_name: &str, namespace: &str, imports: &[&str]) -> ModelFile {
+  ModelFile {
+    file_name: file_name.to_string(),
+    namespace: namespace.to_string(),
+    imports: imports.iter().map(|i| i.to_string()).collect(),
+  }
+}
+
+fn file_names(models: &[ModelFile]) -> Vec<&str> {
+  models.iter().map(|m| m.file_name.as_str()).collect()
+}
+
+#[test]
+fn test_parse_model() {
+  let model_file = parse_model("/tck/a.dmn", IMPORTING).unwrap();
+  assert_eq!("http://www.trisotech.com/definitions/_importing", model_file.namespace);
+  assert_eq!(
+    vec!["http://www.trisotech.com/definitions/_imported"],
+    model_file.imports
+  );
+}
+
+#[test]
+fn test_order_imported_first() {
+  let models = vec![
+    model("/tck/1/a.dmn", "a", &["c"]),
+    model("/tck/1/b.dmn", "b", &[]),
+    model("/tck/1/c.dmn", "c", &["b"]),
+    model("/tck/1/d.dmn", "d", &[]),
+  ];
+  let ordered = deployment_order(&models).unwrap();
+  assert_eq!(
+    vec!["/tck/1/b.dmn", "/tck/1/c.dmn", "/tck/1/a.dmn", "/tck/1/d.dmn"],
+    file_names(&ordered)
+  );
+}
+
+#[test]
+fn test_order_prefers_same_directory() {
+  let models = vec![
+    model("/tck/1/a.dmn", "a", &["x"]),
+    model("/tck/1/x.dmn", "x", &[]),
+    model("/tck/2/a.dmn", "a", &["x"]),
+    model("/tck/2/x.dmn", "x", &[]),
+  ];
+  let ordered = deployment_order(&models).unwrap();
+  assert_eq!(
+    vec!["/tck/1/x.dmn", "/tck/1/a.dmn", "/tck/2/x.dmn", "/tck/2/a.dmn"],
+    file_names(&ordered)
+  );
+}
+
+#[test]
+fn test_missing_import() {
+  let models = vec![model("/tck/1/a.dmn", "a", &["missing"])];
+  assert_eq!(
+    Err(RunnerError::ImportNotFound(
+      "model 'a.dmn' imports namespace 'missing' not defined in any model".to_string()
+    )),
+    deployment_order(&models)
+  );
+}
+
+#[test]
+fn test_cyclic_imports() {
+  let models = vec![
+    model("/tck/1/a.dmn", "a", &["b"]),
+    model("/tck/1/b.dmn", "b", &["a"]),
+    model("/tck/1/c.dmn", "c", &[]),
+  ];
+  assert_eq!(
+    Err(RunnerError::CyclicImports("a.dmn, b.dmn".to_string())),
+    deployment_order(&models)
+  );
+}
+
+#[test]
+fn test_invalid_models() {
+  let models = vec![
+    model("/tck/1/a.dmn", "a", &["b"]),
+    model("/tck/1/b.dmn", "b", &["missing"]),
+    model("/tck/1/c.dmn", "c", &["d"]),
+    model("/tck/1/d.dmn", "d", &["e"]),
+    model("/tck/1/e.dmn", "e", &["d"]),
+    model("/tck/1/f.dmn", "f", &[]),
+  ];
+  let invalid = invalid_models(&models);
+  assert_eq!(
+    vec![
+      "/tck/1/a.dmn",
+      "/tck/1/b.dmn",
+      "/tck/1/c.dmn",
+      "/tck/1/d.dmn",
+      "/tck/1/e.dmn"
+    ],
+    invalid.keys().collect::<Vec<&String>>()
+  );
+  assert_eq!("imported model 'b.dmn' can not be deployed", invalid["/tck/1/a.dmn"]);
+  assert!(invalid["/tck/1/b.dmn"].starts_with("ImportNotFound"));
+  assert_eq!("CyclicImports(\"c.dmn, d.dmn, e.dmn\")", invalid["/tck/1/c.dmn"]);
+  assert!(invalid_models(&models[5..]).is_empty());
+}
+
+#[test]
+fn test_model_tag() {
+  let root_dir = Path::new("/tck/TestCases");
+  assert_eq!(
+    "compliance-level-3/0086-import/0086-import.dmn",
+    model_tag(
+      root_dir,
+      "/tck/TestCases/compliance-level-3/0086-import",
+      "0086-import.dmn"
+    )
+  );
+  assert_eq!("a.dmn", model_tag(root_dir, "/tck/TestCases", "a.dmn"));
+  assert_eq!("/other/a.dmn", model_tag(root_dir, "/other", "a.dmn"));
+}
+
+#[test]
+fn test_required_models() {
+  let models = vec![
+    model("/tck/1/a.dmn", "a", &["b"]),
+    model("/tck/1/b.dmn", "b", &["c"]),
+    model("/tck/1/c.dmn", "c", &[]),
+    model("/tck/1/d.dmn", "d", &[]),
+    model("/tck/2/e.dmn", "e", &["missing"]),
+  ];
+  let selected: BTreeSet<String> = vec!["/tck/1/a.dmn".to_string()].into_iter().collect();
+  let required = required_models(&models, &selected).unwrap();
+  assert_eq!(
+    vec!["/tck/1/a.dmn", "/tck/1/b.dmn", "/tck/1/c.dmn"],
+    file_names(&required)
+  );
+  let selected: BTreeSet<String> = vec!["/tck/1/d.dmn".to_string()].into_iter().collect();
+  let required = required_models(&models, &selected).unwrap();
+  assert_eq!(vec!["/tck/1/d.dmn"], file_names(&required));
+  let selected: BTreeSet<String> = vec!["/tck/2/e.dmn".to_string()].into_iter().collect();
+  assert!(required_models(&models, &selected).is_err());
+}
+
+#[test]
+fn test_parse_required_models() {
+  let dir = std::env::temp_dir().join(format!("dmn-tck-rs-required-models-{}", std::process::id()));
+  fs::create_dir_all(dir.join("1")).unwrap();
+  fs::create_dir_all(dir.join("2")).unwrap();
+  let file_name = |name: &str| dir.join(name).display().to_string();
+  fs::write(file_name("1/a.dmn"), IMPORTING.replace("_importing", "_a")).unwrap();
+  fs::write(file_name("1/b.dmn"), "<definitions").unwrap();
+  fs::write(file_name("1/c.dmn"), "<definitions").unwrap();
+  fs::write(
+    file_name("2/imported.dmn"),
+    IMPORTING.replace("_importing", "_imported").replace(
+      r#"<import namespace="http://www.trisotech.com/definitions/_imported""#,
+      r#"<import namespace="http://www.example.com/other""#,
+    ),
+  )
+  .unwrap();
+  let dmn_files = vec![
+    file_name("1/a.dmn"),
+    file_name("1/b.dmn"),
+    file_name("1/c.dmn"),
+    file_name("2/imported.dmn"),
+  ];
+  let selected: BTreeSet<String> = vec![file_name("1/a.dmn"), file_name("1/c.dmn")].into_iter().collect();
+  let (models, failures) = parse_required_models(&dmn_files, &selected);
+  fs::remove_dir_all(&dir).unwrap();
+  assert_eq!(
+    vec![file_name("1/a.dmn"), file_name("2/imported.dmn")],
+    models.iter().map(|m| m.file_name.clone()).collect::<Vec<String>>()
+  );
+  assert_eq!(
+    vec![&file_name("1/b.dmn"), &file_name("1/c.dmn")],
+    failures.keys().collect::<Vec<&String>>()
+  );
+}
@@ -0,0 +1,231 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for encoding values as plain JSON.
+
+use crate::compare::{compare_values, CompareOptions};
+use crate::dto::{InputNodeDto, ValueDto};
+use crate::encoding::{
+  decode_result, decode_value, encode_params, encode_value, recover_types, ValueEncoding, TYPE_DECIMAL,
+};
+use crate::params::EvaluateParams;
+use crate::results::ResultDto;
+use serde_json::json;
+
+fn value(json: serde_json::Value) -> ValueDto {
+  serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn test_encode_value() {
+  let typed = value(json!({"components": [
+    {"name": "Amount", "value": {"simple": {"type": "xsd:decimal", "text": "10.50"}}},
+    {"name": "Count", "value": {"simple": {"type": "xsd:integer", "text": "3"}}},
+    {"name": "Active", "value": {"simple": {"type": "xsd:boolean", "text": "true"}}},
+    {"name": "Start", "value": {"simple": {"type": "xsd:date", "text": "2020-01-31"}}},
+    {"name": "Missing", "value": {"simple": {"type": null, "text": null, "isNil": true}}},
+    {"name": "Empty", "isNil": true},
+    {"name": "Items", "value": {"list": {"items": [{"simple": {"type": "xsd:string", "text": "a"}}]}}}
+  ]}));
+  assert_eq!(
+    serde_json::from_str::<serde_json::Value>(
+      r#"{
+        "Amount": 10.50,
+        "Count": 3,
+        "Active": true,
+        "Start": "2020-01-31",
+        "Missing": null,
+        "Empty": null,
+        "Items": ["a"]
+      }"#
+    )
+    .unwrap(),
+    encode_value(&typed).unwrap()
+  );
+}
+
+#[test]
+fn test_encode_params() {
+  let params = EvaluateParams {
+    tag: None,
+    artifact: "bkm".to_string(),
+    name: "Add".to_string(),
+    input: vec![InputNodeDto {
+      name: "a".to_string(),
+      value: Some(value(json!({"simple": {"type": "xsd:decimal", "text": "1"}}))),
+    }],
+    arguments: Some(vec![
+      Some(value(json!({"simple": {"type": "xsd:decimal", "text": "1"}}))),
+      None,
+    ]),
+    result_name: None,
+    cast: None,
+  };
+  let encoded = encode_params(&params, ValueEncoding::Plain).unwrap();
+  assert_eq!(json!([{"name": "a", "value": 1}]), encoded["input"]);
+  assert_eq!(json!([1, null]), encoded["arguments"]);
+  let encoded = encode_params(&params, ValueEncoding::Typed).unwrap();
+  assert_eq!(json!("1"), encoded["input"][0]["value"]["simple"]["text"]);
+}
+
+#[test]
+fn test_decode_and_recover() {
+  let expected = value(json!({"components": [
+    {"name": "Amount", "value": {"simple": {"type": "xsd:decimal", "text": "10.50"}}},
+    {"name": "Active", "value": {"simple": {"type": "xsd:boolean", "text": "true"}}},
+    {"name": "Tags", "value": {"list": {"items": [], "isNil": true}}},
+    {"name": "Items", "value": {"list": {"items": [{"simple": {"type": "xsd:string", "text": "a"}}]}}}
+  ]}));
+  let actual = decode_value(&json!({"Active": true, "Amount": 10.5, "Items": ["a"], "Tags": null}));
+  assert!(
+    actual.components.as_ref().unwrap()[1]
+      .value
+      .as_ref()
+      .unwrap()
+      .simple
+      .as_ref()
+      .unwrap()
+      .typ
+      .as_deref()
+      == Some(TYPE_DECIMAL)
+  );
+  let options = CompareOptions::default();
+  assert!(!compare_values(&actual, &expected, &options));
+  assert!(compare_values(&recover_types(&actual, &expected), &expected, &options));
+  let actual = decode_value(&json!({"Active": true, "Amount": 10.51, "Items": ["a"], "Tags": null}));
+  assert!(!compare_values(&recover_types(&actual, &expected), &expected, &options));
+}
+
+#[test]
+fn test_decode_result() {
+  let result = ResultDto {
+    data: Some(json!({"value": "Hello"})),
+    errors: None,
+  };
+  let decoded = decode_result(result, ValueEncoding::Plain).unwrap();
+  let simple = decoded.data.unwrap().value.unwrap().simple.unwrap();
+  assert_eq!(Some("Hello".to_string()), simple.text);
+  let result = ResultDto {
+    data: Some(json!("Hello")),
+    errors: None,
+  };
+  assert!(decode_result(result, ValueEncoding::Plain).is_err());
+}
+
+#[test]
+fn test_high_precision_decimal() {
+  let typed = value(json!({"simple": {"type": "xsd:decimal", "text": "12345678901234567890.123456789012345678901"}}));
+  let encoded = encode_value(&typed).unwrap();
+  assert_eq!(
+    "12345678901234567890.123456789012345678901",
+    serde_json::to_string(&encoded).unwrap()
+  );
+  let typed = value(json!({"simple": {"type": "xsd:decimal", "text": ".5"}}));
+  assert_eq!("0.5", serde_json::to_string(&encode_value(&typed).unwrap()).unwrap());
+  let actual = decode_value(&serde_json::from_str("0.1000000000000000000000000000001").unwrap());
+  let expected = value(json!({"simple": {"type": "xsd:decimal", "text": "0.1000000000000000000000000000001"}}));
+  let options = CompareOptions::default();
+  assert!(compare_values(&recover_types(&actual, &expected), &expected, &options));
+  let actual = decode_value(&serde_json::from_str("0.1").unwrap());
+  assert!(!compare_values(&recover_types(&actual, &expected), &expected, &options));
+}
+
+#[test]
+fn test_recover_only_matching_kinds() {
+  let options = CompareOptions::default();
+  let expected = value(json!({"simple": {"type": "xsd:decimal", "text": "10"}}));
+  assert!(compare_values(
+    &recover_types(&decode_value(&json!(10)), &expected),
+    &expected,
+    &options
+  ));
+  assert!(!compare_values(
+    &recover_types(&decode_value(&json!("10")), &expected),
+    &expected,
+    &options
+  ));
+  let expected = value(json!({"simple": {"type": "xsd:boolean", "text": "true"}}));
+  assert!(compare_values(
+    &recover_types(&decode_value(&json!(true)), &expected),
+    &expected,
+    &options
+  ));
+  assert!(!compare_values(
+    &recover_types(&decode_value(&json!("true")), &expected),
+    &expected,
+    &options
+  ));
+  let expected = value(json!({"simple": {"type": "xsd:date", "text": "2020-01-31"}}));
+  assert!(compare_values(
+    &recover_types(&decode_value(&json!("2020-01-31")), &expected),
+    &expected,
+    &options
+  ));
+  let expected = value(json!({"simple": {"type": "xsd:string", "text": "10"}}));
+  assert!(!compare_values(
+    &recover_types(&decode_value(&json!(10)), &expected),
+    &expected,
+    &options
+  ));
+}
+
+#[test]
+fn test_nil_component_round_trip() {
+  let expected = value(json!({"components": [
+    {"name": "Empty", "isNil": true},
+    {"name": "Missing", "value": {"simple": {"type": null, "text": null, "isNil": true}}}
+  ]}));
+  let encoded = encode_value(&expected).unwrap();
+  assert_eq!(json!({"Empty": null, "Missing": null}), encoded);
+  let actual = recover_types(&decode_value(&encoded), &expected);
+  assert!(compare_values(&actual, &expected, &CompareOptions::default()));
+}
+
+#[test]
+fn test_component_order() {
+  let expected = value(json!({"components": [
+    {"name": "Zone", "value": {"simple": {"type": "xsd:string", "text": "B"}}},
+    {"name": "Amount", "value": {"simple": {"type": "xsd:decimal", "text": "10"}}}
+  ]}));
+  let encoded = encode_value(&expected).unwrap();
+  assert_eq!(r#"{"Zone":"B","Amount":10}"#, encoded.to_string());
+  let decoded = serde_json::from_str(r#"{"Zone": "B", "Amount": 10}"#).unwrap();
+  let actual = recover_types(&decode_value(&decoded), &expected);
+  let names: Vec<_> = actual
+    .components
+    .iter()
+    .flatten()
+    .map(|component| component.name.clone().unwrap())
+    .collect();
+  assert_eq!(vec!["Zone", "Amount"], names);
+  let options = CompareOptions {
+    ordered_components: true,
+    recover_types: true,
+  };
+  assert!(compare_values(&actual, &expected, &options));
+}
+
+#[test]
+fn test_duplicated_components() {
+  let typed = value(json!({"components": [
+    {"name": "Amount", "value": {"simple": {"type": "xsd:decimal", "text": "10"}}},
+    {"name": "Amount", "value": {"simple": {"type": "xsd:decimal", "text": "20"}}}
+  ]}));
+  assert!(matches!(
+    encode_value(&typed),
+    Err(crate::errors::RunnerError::SerializingFailed(reason)) if reason.contains("'Amount'")
+  ));
+}
@@ -0,0 +1,217 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for engine adapters.
+
+use crate::config::HttpParams;
+use crate::encoding::ValueEncoding;
+use crate::engine::process::ProcessEngine;
+use crate::engine::rest::{build_client, default_headers};
+use crate::engine::Engine;
+use crate::errors::RunnerError;
+use crate::params::{DeployParams, EvaluateParams};
+use native_tls::{Identity, TlsAcceptor};
+use reqwest::header::AUTHORIZATION;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+/// Self-signed certificate issued for `localhost` and `127.0.0.1`.
+const LOCALHOST_CERTIFICATE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/localhost.pem");
+
+/// Identity of the TLS server with the self-signed certificate, protected with password `secret`.
+const LOCALHOST_IDENTITY: &[u8] = include_bytes!("localhost.p12");
+
+fn shell(script: &str, timeout_ms: u64) -> ProcessEngine {
+  ProcessEngine::new(
+    vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+    Duration::from_millis(timeout_ms),
+    ValueEncoding::Typed,
+  )
+}
+
+fn evaluate_params(name: &str) -> EvaluateParams {
+  EvaluateParams {
+    tag: Some("model.dmn".to_string()),
+    artifact: "decision".to_string(),
+    name: name.to_string(),
+    input: vec![],
+    arguments: None,
+    result_name: None,
+    cast: None,
+  }
+}
+
+fn deploy_params(tag: &str) -> DeployParams {
+  DeployParams {
+    source: None,
+    content: None,
+    tag: Some(tag.to_string()),
+  }
+}
+
+#[test]
+fn test_process_evaluate() {
+  let mut engine = shell(
+    r#"while read line; do echo '{"data":{"value":{"simple":{"type":"xsd:string","text":"Hello","isNil":false}}}}'; done"#,
+    5000,
+  );
+  assert_eq!(Ok(()), engine.health());
+  let result = engine.evaluate(&evaluate_params("Greeting")).unwrap();
+  let value = result.data.unwrap().value.unwrap();
+  assert_eq!(Some("Hello"), value.simple.unwrap().text.as_deref());
+}
+
+#[test]
+fn test_process_errors() {
+  let mut engine = shell(
+    r#"while read line; do echo '{"errors":[{"details":"unknown model"}]}'; done"#,
+    5000,
+  );
+  let result = engine.evaluate(&evaluate_params("Greeting")).unwrap();
+  assert_eq!("unknown model", result.errors_as_string());
+  assert!(engine.health().is_err());
+}
+
+#[test]
+fn test_process_timeout() {
+  let mut engine = shell("sleep 10", 200);
+  assert_eq!(
+    Err(RunnerError::TransportFailed(
+      "engine process did not respond within 200 ms".to_string()
+    )),
+    engine.evaluate(&evaluate_params("Greeting")).map(|_| ())
+  );
+}
+
+#[test]
+fn test_process_restart() {
+  let log_file = std::env::temp_dir().join(format!("dmn-tck-rs-process-engine-{}.log", std::process::id()));
+  let _ = std::fs::remove_file(&log_file);
+  let script = format!(
+    r#"while read line; do echo "$line" >> '{}'; case "$line" in *crash*) exit 1;; esac; echo '{{"data":{{}}}}'; done"#,
+    log_file.display()
+  );
+  let mut engine = shell(&script, 5000);
+  assert!(engine.deploy(&deploy_params("a.dmn")).unwrap().data.is_some());
+  assert_eq!(
+    Err(RunnerError::TransportFailed("engine process terminated".to_string())),
+    engine.evaluate(&evaluate_params("crash")).map(|_| ())
+  );
+  assert!(engine.evaluate(&evaluate_params("Greeting")).is_ok());
+  let log = std::fs::read_to_string(&log_file).unwrap();
+  let operations: Vec<&str> = log
+    .lines()
+    .map(|line| {
+      if line.contains(r#""deploy""#) {
+        "deploy"
+      } else {
+        "evaluate"
+      }
+    })
+    .collect();
+  assert_eq!(vec!["deploy", "evaluate", "deploy", "evaluate"], operations);
+  std::fs::remove_file(&log_file).unwrap();
+}
+
+#[test]
+fn test_default_headers() {
+  let mut http = HttpParams {
+    basic_auth_user: Some("user".to_string()),
+    basic_auth_password: Some("secret".to_string()),
+    ..Default::default()
+  };
+  http.headers.insert("X-Tenant".to_string(), "tck".to_string());
+  let headers = default_headers(&http).unwrap();
+  assert_eq!("tck", headers.get("x-tenant").unwrap());
+  assert_eq!("Basic dXNlcjpzZWNyZXQ=", headers.get(AUTHORIZATION).unwrap());
+  http.bearer_token = Some("token".to_string());
+  let headers = default_headers(&http).unwrap();
+  assert_eq!("Bearer token", headers.get(AUTHORIZATION).unwrap());
+  http.headers.insert("Invalid Name".to_string(), "x".to_string());
+  assert_eq!(
+    Err(RunnerError::InvalidConfiguration(
+      "invalid header name 'Invalid Name'".to_string()
+    )),
+    default_headers(&http).map(|_| ())
+  );
+  let http = HttpParams {
+    bearer_token: Some("secret\ntoken".to_string()),
+    ..Default::default()
+  };
+  assert_eq!(
+    Err(RunnerError::InvalidConfiguration(
+      "invalid value of header 'authorization'".to_string()
+    )),
+    default_headers(&http).map(|_| ())
+  );
+}
+
+#[test]
+fn test_build_client() {
+  let mut http = HttpParams {
+    timeout_ms: Some(1000),
+    accept_invalid_certificates: true,
+    ..Default::default()
+  };
+  assert!(build_client(&http).is_ok());
+  http.ca_certificate_file_path = Some("/non/existing/ca.pem".to_string());
+  assert!(build_client(&http).is_err());
+}
+
+/// Starts the TLS server with the self-signed certificate, answering specified number
+/// of requests with an empty JSON object. Returns the URL of the server.
+fn tls_server(requests: usize) -> String {
+  let identity = Identity::from_pkcs12(LOCALHOST_IDENTITY, "secret").unwrap();
+  let acceptor = TlsAcceptor::new(identity).unwrap();
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let url = format!("https://127.0.0.1:{}/health", listener.local_addr().unwrap().port());
+  thread::spawn(move || {
+    for stream in listener.incoming().take(requests).flatten() {
+      if let Ok(stream) = acceptor.accept(stream) {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        while reader.read_line(&mut line).is_ok_and(|count| count > 0) && !line.trim().is_empty() {
+          line.clear();
+        }
+        let mut stream = reader.into_inner();
+        let _ = write!(
+          stream,
+          "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{{}}"
+        );
+        let _ = stream.flush();
+      }
+    }
+  });
+  url
+}
+
+#[test]
+fn test_self_signed_certificate() {
+  let url = tls_server(2);
+  let http = HttpParams {
+    timeout_ms: Some(5000),
+    ..Default::default()
+  };
+  assert!(build_client(&http).unwrap().get(&url).send().is_err());
+  let http = HttpParams {
+    ca_certificate_file_path: Some(LOCALHOST_CERTIFICATE.to_string()),
+    ..http
+  };
+  let response = build_client(&http).unwrap().get(&url).send().unwrap();
+  assert!(response.status().is_success());
+}
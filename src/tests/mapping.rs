@@ -0,0 +1,93 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for mapping requests and responses.
+
+use crate::config::OperationMapping;
+use crate::dto::ActualValueDto;
+use crate::engine::mapping::{extract, render, render_text, render_url};
+use crate::results::DeployResult;
+use serde_json::json;
+
+#[test]
+fn test_render() {
+  let context = json!({"tag": "a/b", "name": "Greeting", "inputs": {"Full Name": {"simple": {"text": "John"}}}});
+  let template = json!({
+    "decisionKey": "${/name}",
+    "label": "decision ${/name} in ${/tag}",
+    "variables": "${/inputs}",
+    "missing": "${/unknown}",
+    "fixed": [1, true, null]
+  });
+  assert_eq!(
+    json!({
+      "decisionKey": "Greeting",
+      "label": "decision Greeting in a/b",
+      "variables": {"Full Name": {"simple": {"text": "John"}}},
+      "missing": null,
+      "fixed": [1, true, null]
+    }),
+    render(&template, &context)
+  );
+  assert_eq!(
+    "decision Greeting in a/b",
+    render_text("decision ${/name} in ${/tag}", &context)
+  );
+  assert_eq!(
+    "http://localhost/decision/Greeting/evaluate?tag=a%2Fb",
+    render_url("http://localhost/decision/${/name}/evaluate?tag=${/tag}", &context)
+  );
+  let context = json!({"name": "Greeting Message?", "tag": "łódź"});
+  assert_eq!(
+    "http://localhost/Greeting%20Message%3F/%C5%82%C3%B3d%C5%BA",
+    render_url("http://localhost/${/name}/${/tag}", &context)
+  );
+}
+
+#[test]
+fn test_extract_default() {
+  let response = json!({"data": {"name": "model", "id": "1"}, "errors": [{"details": "warning"}]});
+  let result = extract::<DeployResult>(&response, None).unwrap();
+  assert_eq!("warning", result.errors_as_string());
+  assert_eq!(Some("1".to_string()), result.data.unwrap().id);
+}
+
+#[test]
+fn test_extract_mapped() {
+  let mapping = OperationMapping {
+    result_pointer: Some("/result/0".to_string()),
+    errors_pointer: Some("/problems".to_string()),
+    ..Default::default()
+  };
+  let response = json!({"result": [{"value": {"simple": {"text": "Hello"}}}]});
+  let result = extract::<ActualValueDto>(&response, Some(&mapping)).unwrap();
+  assert!(result.errors.is_none());
+  let value = result.data.unwrap().value.unwrap();
+  assert_eq!(Some("Hello".to_string()), value.simple.unwrap().text);
+  let response = json!({"problems": [{"message": "unknown decision"}, "timeout", 42]});
+  let result = extract::<ActualValueDto>(&response, Some(&mapping)).unwrap();
+  assert!(result.data.is_none());
+  assert_eq!("unknown decision, timeout, 42", result.errors_as_string());
+  let response = json!({"problems": {"message": "unknown decision"}});
+  let result = extract::<ActualValueDto>(&response, Some(&mapping)).unwrap();
+  assert_eq!("unknown decision", result.errors_as_string());
+}
+
+#[test]
+fn test_extract_invalid_result() {
+  let response = json!({"data": {"value": 12}});
+  assert!(extract::<ActualValueDto>(&response, None).is_err());
+}
@@ -16,13 +16,30 @@
 
 //! Test case input files.
 
+mod benchmark;
+mod cache;
+mod compare;
+mod config;
+mod definitions;
+#[allow(unused_imports)]
 mod dto;
+mod encoding;
+mod engine;
+mod mapping;
+#[allow(clippy::bool_assert_comparison, clippy::assertions_on_constants)]
 mod model;
+mod params;
+mod runner;
+mod server;
+mod suite;
+mod writer;
 
 const TC_0001: &str = include_str!("tc-0001.xml");
 const TC_0002: &str = include_str!("tc-0002.xml");
 const TC_0003: &str = include_str!("tc-0003.xml");
 
+const TC_0001_YAML: &str = include_str!("tc-0001.tck.yaml");
+
 const INPUTS_0001: &str = include_str!("inputs-0001.json");
 const INPUTS_0002: &str = include_str!("inputs-0002.json");
 const INPUTS_0003: &str = include_str!("inputs-0003.json");
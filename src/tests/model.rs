@@ -123,3 +123,31 @@ fn test_003() {
     _ => assert!(false),
   }
 }
+
+#[test]
+fn test_components_document_order() {
+  let test_cases = parse_from_string(
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+    <testCases xmlns="http://www.omg.org/spec/DMN/20160719/testcase"
+               xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+               xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+        <testCase id="001">
+            <resultNode name="result" type="decision">
+                <expected>
+                    <component name="b"><value xsi:type="xsd:decimal">1</value></component>
+                    <component name="a"><value xsi:type="xsd:decimal">2</value></component>
+                    <component name="b"><value xsi:type="xsd:decimal">3</value></component>
+                </expected>
+            </resultNode>
+        </testCase>
+    </testCases>"#,
+  )
+  .unwrap();
+  match &test_cases.test_cases[0].result_nodes[0].expected {
+    Some(Value::Components(components)) => {
+      let names: Vec<&str> = components.iter().map(|c| c.name.as_ref().unwrap().as_str()).collect();
+      assert_eq!(vec!["b", "a", "b"], names);
+    }
+    _ => panic!(),
+  }
+}
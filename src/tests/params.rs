@@ -0,0 +1,91 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for evaluation parameters.
+
+use crate::model::{parse_from_string, TestCaseType};
+use crate::runner::evaluate_params;
+use crate::tests::TC_0001;
+
+const INVOCABLES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testCases xmlns="http://www.omg.org/spec/DMN/20160719/testcase"
+           xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+           xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+    <modelName>0099-invocables.dmn</modelName>
+    <testCase id="001" type="bkm" invocableName="sum">
+        <inputNode name="a"><value xsi:type="xsd:decimal">1</value></inputNode>
+        <inputNode name="b"><value xsi:type="xsd:decimal">2</value></inputNode>
+        <resultNode name="sum" type="bkm">
+            <expected><value xsi:type="xsd:decimal">3</value></expected>
+        </resultNode>
+    </testCase>
+    <testCase id="002" type="decisionService" invocableName="service">
+        <inputNode name="input"><value xsi:type="xsd:string">x</value></inputNode>
+        <resultNode name="output" type="decision">
+            <expected><value xsi:type="xsd:string">y</value></expected>
+        </resultNode>
+    </testCase>
+</testCases>"#;
+
+#[test]
+fn test_decision_params() {
+  let test_cases = parse_from_string(TC_0001).unwrap();
+  let test_case = &test_cases.test_cases[0];
+  let params = evaluate_params(&test_cases.model_name, test_case, &test_case.result_nodes[0]);
+  assert_eq!("decision", params.artifact);
+  assert_eq!("Greeting Message", params.name);
+  assert_eq!(Some("0001-input-data-string.dmn".to_string()), params.tag);
+  assert_eq!(1, params.input.len());
+  assert_eq!(None, params.arguments);
+  assert_eq!(None, params.result_name);
+}
+
+#[test]
+fn test_bkm_params() {
+  let test_cases = parse_from_string(INVOCABLES).unwrap();
+  let test_case = &test_cases.test_cases[0];
+  assert_eq!(TestCaseType::BusinessKnowledgeModel, test_case.typ);
+  let params = evaluate_params(&test_cases.model_name, test_case, &test_case.result_nodes[0]);
+  assert_eq!("bkm", params.artifact);
+  assert_eq!("sum", params.name);
+  assert_eq!(
+    vec!["a", "b"],
+    params.input.iter().map(|i| i.name.as_str()).collect::<Vec<&str>>()
+  );
+  let arguments = params.arguments.unwrap();
+  assert_eq!(2, arguments.len());
+  assert_eq!(
+    Some("1"),
+    arguments[0].as_ref().unwrap().simple.as_ref().unwrap().text.as_deref()
+  );
+  assert_eq!(
+    Some("2"),
+    arguments[1].as_ref().unwrap().simple.as_ref().unwrap().text.as_deref()
+  );
+  assert_eq!(Some("sum".to_string()), params.result_name);
+}
+
+#[test]
+fn test_decision_service_params() {
+  let test_cases = parse_from_string(INVOCABLES).unwrap();
+  let test_case = &test_cases.test_cases[1];
+  assert_eq!(TestCaseType::DecisionService, test_case.typ);
+  let params = evaluate_params(&test_cases.model_name, test_case, &test_case.result_nodes[0]);
+  assert_eq!("decisionService", params.artifact);
+  assert_eq!("service", params.name);
+  assert_eq!(None, params.arguments);
+  assert_eq!(Some("output".to_string()), params.result_name);
+}
@@ -0,0 +1,1244 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! End to end test cases of the runner pipeline, run against the mock engine server.
+
+use crate::config::{
+  BenchmarkFormat, BenchmarkParams, ConfigurationParams, EngineDownAction, FailFastParams, OperationMapping,
+};
+use crate::definitions::model_tag;
+use crate::dto::ActualValueDto;
+use crate::engine::cassette::{RecordingEngine, ReplayEngine};
+use crate::engine::rest::RestEngine;
+use crate::engine::Engine;
+use crate::errors::RunnerError;
+use crate::model::parse_from_string;
+use crate::params::{DeployParams, EvaluateParams, UndeployParams, VerifyParams};
+use crate::results::{DeployResult, ResultDto, UndeployResult};
+use crate::runner::{
+  deploy_dmn_definitions, execute_tests, stop_reason, undeploy_dmn_definitions, wait_until_ready, BenchmarkReport,
+  CancellationToken, Checkpoint, CsvReport, DeploymentReport, Deployments, Event, ExecutionOptions, Listener,
+  RunSummary, Runner, TestFile, TestOutcome, TestStatus,
+};
+use crate::tests::server::{Fault, MockServer};
+use crate::tests::TC_0001;
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const MODEL_NAME: &str = "0001-input-data-string.dmn";
+
+const MODEL_CONTENT: &str = r#"<definitions xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/" namespace="https://example.com/0001" name="0001"/>"#;
+
+const DECISION_NAME: &str = "Greeting Message";
+
+/// Number of test directories created so far, used to make directory names unique.
+static TEST_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+/// Test directory containing the model and test file, removed when dropped.
+struct TestDir {
+  path: PathBuf,
+}
+
+impl TestDir {
+  fn new(name: &str) -> Self {
+    let path = std::env::temp_dir().join(format!(
+      "dmn-tck-rs-runner-{}-{}-{}",
+      name,
+      std::process::id(),
+      TEST_DIRS.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join(MODEL_NAME), MODEL_CONTENT).unwrap();
+    fs::write(path.join("0001-input-data-string-test-01.xml"), TC_0001).unwrap();
+    let path = path.canonicalize().unwrap();
+    Self { path }
+  }
+
+  fn file(&self, name: &str) -> String {
+    self.path.join(name).display().to_string()
+  }
+
+  fn tag(&self) -> String {
+    model_tag(&self.path, &self.path.display().to_string(), MODEL_NAME)
+  }
+
+  /// Executes tests from the test file, returns lines of the report.
+  fn execute(&self, engine: &mut dyn Engine, deployments: &Deployments) -> Vec<String> {
+    let report_file = self.file("report.csv");
+    let mut report = CsvReport::new(&report_file).unwrap();
+    execute_tests(
+      &TestFile::read(&self.file("0001-input-data-string-test-01.xml")).unwrap(),
+      &self.path,
+      engine,
+      &ExecutionOptions::default(),
+      deployments,
+      &mut report,
+      &CancellationToken::default(),
+    )
+    .unwrap();
+    fs::read_to_string(report_file)
+      .unwrap()
+      .lines()
+      .map(|line| line.to_string())
+      .collect()
+  }
+}
+
+impl Drop for TestDir {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.path);
+  }
+}
+
+/// Prepares configuration of the run against the mock server.
+fn config(server: &MockServer, dir: &str, timeout_ms: u64) -> ConfigurationParams {
+  serde_yaml::from_str(&format!(
+    r#"
+test_cases_dir_path: {}
+file_name_pattern: ""
+deploy_url: {}
+evaluate_url: {}
+undeploy_url: {}
+health_url: {}
+report_file_path: report.csv
+stop_on_failure: false
+http:
+  timeout_ms: {}
+"#,
+    dir,
+    server.url("deploy"),
+    server.url("evaluate"),
+    server.url("undeploy"),
+    server.url("health"),
+    timeout_ms
+  ))
+  .unwrap()
+}
+
+fn engine(server: &MockServer, timeout_ms: u64) -> RestEngine {
+  RestEngine::new(&config(server, ".", timeout_ms)).unwrap()
+}
+
+/// Listener collecting all received events.
+#[derive(Clone, Default)]
+struct Collector {
+  events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl Listener for Collector {
+  fn on_event(&mut self, event: &Event) -> crate::errors::Result<()> {
+    self.events.borrow_mut().push(event.clone());
+    Ok(())
+  }
+}
+
+/// Returns the test file with the test case repeated with identifier 002.
+fn two_tests() -> String {
+  let test_case = &TC_0001[TC_0001.find("    <testCase").unwrap()..TC_0001.find("</testCases>").unwrap()];
+  TC_0001.replace(
+    "</testCases>",
+    &format!("{}</testCases>", test_case.replace("001", "002")),
+  )
+}
+
+/// Returns identifiers and statuses of evaluated tests.
+fn statuses(events: &[Event]) -> Vec<(String, TestStatus)> {
+  events
+    .iter()
+    .filter_map(|event| match event {
+      Event::TestEvaluated(outcome) => Some((outcome.test_id.clone(), outcome.status)),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Starts the server answering test cases from the test file in specified directory.
+fn server(dir: &TestDir) -> MockServer {
+  let server = MockServer::start();
+  server.answer_test_cases(&dir.tag(), &parse_from_string(TC_0001).unwrap());
+  server
+}
+
+#[test]
+fn test_deploy_evaluate_undeploy() {
+  let dir = TestDir::new("success");
+  let server = server(&dir);
+  let mut engine = engine(&server, 5000);
+  let id = deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  assert_eq!(Some("model-1".to_string()), id);
+  let deployed = server.requests("deploy");
+  assert_eq!(1, deployed.len());
+  assert_eq!(base64::encode(MODEL_CONTENT), deployed[0]["content"]);
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert_eq!(1, report.len());
+  assert!(report[0].contains(r#""0001-input-data-string-test-01.xml","001","SUCCESS","",""#));
+  assert_eq!(Ok(true), undeploy_dmn_definitions(&dir.tag(), &mut engine));
+  assert_eq!(1, server.requests("undeploy").len());
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""FAILURE","model '"#));
+}
+
+#[test]
+fn test_evaluate_request() {
+  let dir = TestDir::new("evaluate-request");
+  let server = server(&dir);
+  let test_cases = parse_from_string(TC_0001).unwrap();
+  let test_case = &test_cases.test_cases[0];
+  let params = crate::runner::evaluate_params(&Some(dir.tag()), test_case, &test_case.result_nodes[0]);
+  // default request contains evaluation parameters only
+  let mut engine = engine(&server, 5000);
+  engine.evaluate(&params).unwrap();
+  assert_eq!(serde_json::to_value(&params).unwrap(), server.requests("evaluate")[0]);
+  // input values keyed by input name are available in templates
+  let mut config = config(&server, ".", 5000);
+  config.mapping.evaluate = Some(OperationMapping {
+    request: Some(serde_json::json!({ "decision": "${/name}", "variables": "${/inputs}" })),
+    ..Default::default()
+  });
+  let mut engine = RestEngine::new(&config).unwrap();
+  let _ = engine.evaluate(&params);
+  assert_eq!(
+    serde_json::json!({
+      "decision": DECISION_NAME,
+      "variables": { "Full Name": { "simple": { "type": "xsd:string", "text": "John Doe", "isNil": false } } }
+    }),
+    server.requests("evaluate")[1]
+  );
+}
+
+#[test]
+fn test_deployment_failed() {
+  let dir = TestDir::new("deployment-failed");
+  let server = server(&dir);
+  server.inject("deploy", &dir.tag(), Fault::Error("invalid model".to_string()));
+  let mut engine = engine(&server, 5000);
+  assert_eq!(
+    Err(RunnerError::DeploymentFailed("invalid model".to_string())),
+    deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine)
+  );
+  let mut deployments = Deployments::default();
+  deployments.errors.insert(dir.tag(), "invalid model".to_string());
+  let report = dir.execute(&mut engine, &deployments);
+  assert!(report[0].ends_with(r#""001","SKIPPED","deployment failed: invalid model","""#));
+  assert!(server.requests("evaluate").is_empty());
+}
+
+#[test]
+fn test_evaluation_error() {
+  let dir = TestDir::new("evaluation-error");
+  let server = server(&dir);
+  server.inject("evaluate", DECISION_NAME, Fault::Error("division by zero".to_string()));
+  let mut engine = engine(&server, 5000);
+  deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""001","FAILURE","division by zero",""#));
+}
+
+#[test]
+fn test_unexpected_value() {
+  let dir = TestDir::new("unexpected-value");
+  let server = server(&dir);
+  let response = serde_json::json!({"data": {"value": {"simple": {"type": "xsd:string", "text": "Hi John Doe"}}}});
+  server.inject("evaluate", DECISION_NAME, Fault::Response(response));
+  let mut engine = engine(&server, 5000);
+  deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""001","FAILURE","actual <> expected",""#));
+}
+
+#[test]
+fn test_malformed_response() {
+  let dir = TestDir::new("malformed-response");
+  let server = server(&dir);
+  server.inject("evaluate", DECISION_NAME, Fault::Malformed);
+  let mut engine = engine(&server, 5000);
+  deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""001","FAILURE","#));
+}
+
+#[test]
+fn test_delayed_response() {
+  let dir = TestDir::new("delayed-response");
+  let server = server(&dir);
+  server.inject("evaluate", DECISION_NAME, Fault::Delay(Duration::from_millis(2000)));
+  let mut engine = engine(&server, 300);
+  deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""001","FAILURE","#));
+  assert!(report[0].contains("timed out"));
+}
+
+#[test]
+fn test_record_and_replay() {
+  let dir = TestDir::new("record-and-replay");
+  let cassette_dir = dir.file("cassette");
+  {
+    let server = server(&dir);
+    server.inject("deploy", "unknown.dmn", Fault::Delay(Duration::from_millis(2000)));
+    let mut config = config(&server, ".", 300);
+    config
+      .http
+      .headers
+      .insert("X-Api-Key".to_string(), "secret-key".to_string());
+    config.http.bearer_token = Some("secret-token".to_string());
+    let mut engine = RecordingEngine::new(Box::new(RestEngine::new(&config).unwrap()), &cassette_dir);
+    deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+    assert!(deploy_dmn_definitions(&dir.file(MODEL_NAME), "unknown.dmn", &mut engine).is_err());
+    let report = dir.execute(&mut engine, &Deployments::default());
+    assert!(report[0].contains(r#""001","SUCCESS","",""#));
+    dir.execute(&mut engine, &Deployments::default());
+  }
+  assert_eq!(4, fs::read_dir(&cassette_dir).unwrap().count());
+  let recording_path = |index: usize| {
+    PathBuf::from(&cassette_dir).join(format!(
+      "evaluate-0001_input_data_string_test_01.xml-001-Greeting_Message-{}.json",
+      index
+    ))
+  };
+  let recording: serde_json::Value = serde_json::from_str(&fs::read_to_string(recording_path(1)).unwrap()).unwrap();
+  let raw_request = recording["rawRequest"].as_str().unwrap();
+  assert!(raw_request.starts_with("POST /evaluate HTTP/1.1\r\n"));
+  assert!(raw_request.contains("\r\ncontent-type: application/json\r\n"));
+  assert!(raw_request.contains(r#""name":"Greeting Message""#));
+  assert!(raw_request.contains("\r\nx-api-key: <hidden>\r\n"));
+  assert!(raw_request.contains("\r\nauthorization: <hidden>\r\n"));
+  assert!(!raw_request.contains("secret"));
+  let raw_response = recording["rawResponse"].as_str().unwrap();
+  assert!(raw_response.starts_with("HTTP/1.1 200 OK\r\n"));
+  assert!(raw_response.contains("Hello John Doe"));
+  let mut engine = ReplayEngine::new(&cassette_dir);
+  let id = deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  assert_eq!(Some("model-1".to_string()), id);
+  match deploy_dmn_definitions(&dir.file(MODEL_NAME), "unknown.dmn", &mut engine) {
+    Err(RunnerError::DeploymentFailed(reason)) => assert!(reason.contains("TransportFailed")),
+    other => panic!("unexpected result: {:?}", other),
+  }
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""001","SUCCESS","",""#));
+  assert!(matches!(
+    engine.undeploy(&crate::params::UndeployParams { tag: Some(dir.tag()) }),
+    Err(RunnerError::NotSupported(_))
+  ));
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains(r#""001","SUCCESS","",""#));
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains("no recorded response for evaluate request"));
+  let mut recording = recording;
+  recording["request"]["input"][0]["name"] = serde_json::json!("Changed Name");
+  fs::write(recording_path(0), recording.to_string()).unwrap();
+  let mut engine = ReplayEngine::new(&cassette_dir);
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains("ReplayMismatch"));
+  fs::remove_dir_all(&cassette_dir).unwrap();
+  let report = dir.execute(&mut engine, &Deployments::default());
+  assert!(report[0].contains("no recorded response for evaluate request"));
+}
+
+#[test]
+fn test_runner_events() {
+  let dir = TestDir::new("events");
+  let server = server(&dir);
+  let config = config(&server, &dir.path.display().to_string(), 5000);
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let collector = Collector::default();
+  runner.add_listener(Box::new(collector.clone()));
+  let summary = runner.run().unwrap();
+  assert_eq!(1, summary.deployed_models);
+  assert_eq!(1, summary.processed_files);
+  let events = collector.events.borrow();
+  let test_file = dir.file("0001-input-data-string-test-01.xml");
+  assert_eq!(Event::RunStarted(dir.path.display().to_string()), events[0]);
+  match &events[1] {
+    Event::ModelDeployed(tag, id, duration) => {
+      assert_eq!(&dir.tag(), tag);
+      assert_eq!(Some("model-1"), id.as_deref());
+      assert!(*duration > Duration::ZERO);
+    }
+    other => panic!("unexpected event: {:?}", other),
+  }
+  assert_eq!(Event::FileStarted(test_file.clone()), events[2]);
+  match &events[3] {
+    Event::TestEvaluated(outcome) => {
+      assert_eq!("001", outcome.test_id);
+      assert_eq!("Greeting Message", outcome.result_name);
+      assert_eq!(TestStatus::Success, outcome.status);
+      assert_eq!(outcome.expected, outcome.actual);
+      assert!(outcome.duration.is_some());
+    }
+    other => panic!("unexpected event: {:?}", other),
+  }
+  assert_eq!(Event::FileFinished(test_file.clone()), events[4]);
+  assert_eq!(Event::ModelUndeployed(dir.tag()), events[5]);
+  assert_eq!(1, summary.slowest_tests.len());
+  assert_eq!(test_file, summary.slowest_tests[0].file_name);
+  assert_eq!("001", summary.slowest_tests[0].test_id);
+  assert_eq!(Event::RunFinished(summary), events[6]);
+  assert_eq!(7, events.len());
+}
+
+/// Content of the model importing the library model.
+const IMPORTING_MODEL_CONTENT: &str = r#"<definitions xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/" namespace="https://example.com/0001" name="0001">
+  <import namespace="https://example.com/library" name="library" importType="https://www.omg.org/spec/DMN/20191111/MODEL/"/>
+</definitions>"#;
+
+/// Content of the library model imported by models from other directories.
+const LIBRARY_MODEL_CONTENT: &str = r#"<definitions xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/" namespace="https://example.com/library" name="library"/>"#;
+
+/// Prepares directories `a` and `c` with models importing the library model from directory `b`,
+/// returns tags of importing models and the tag of the library model.
+fn import_across_directories(dir: &TestDir, server: &MockServer) -> (Vec<String>, String) {
+  fs::create_dir_all(dir.path.join("b")).unwrap();
+  fs::write(dir.path.join("b").join("library.dmn"), LIBRARY_MODEL_CONTENT).unwrap();
+  let library_tag = model_tag(&dir.path, &dir.file("b"), "library.dmn");
+  let mut tags = vec![];
+  for name in &["a", "c"] {
+    let sub_dir = dir.path.join(name);
+    fs::create_dir_all(&sub_dir).unwrap();
+    fs::write(sub_dir.join(MODEL_NAME), IMPORTING_MODEL_CONTENT).unwrap();
+    fs::write(sub_dir.join("0001-input-data-string-test-01.xml"), TC_0001).unwrap();
+    let tag = model_tag(&dir.path, &dir.file(name), MODEL_NAME);
+    server.answer_test_cases(&tag, &parse_from_string(TC_0001).unwrap());
+    server.import(&tag, &library_tag);
+    tags.push(tag);
+  }
+  (tags, library_tag)
+}
+
+/// Returns tags of models from requests received for specified operation.
+fn requested_tags(server: &MockServer, operation: &str) -> Vec<String> {
+  server
+    .requests(operation)
+    .iter()
+    .map(|request| request["tag"].as_str().unwrap_or_default().to_string())
+    .collect()
+}
+
+#[test]
+fn test_import_across_directories() {
+  let dir = TestDir::new("import-across-directories");
+  let server = server(&dir);
+  let (tags, library_tag) = import_across_directories(&dir, &server);
+  let config = config(&server, &dir.path.display().to_string(), 5000);
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let collector = Collector::default();
+  runner.add_listener(Box::new(collector.clone()));
+  let summary = runner.run().unwrap();
+  assert_eq!(
+    (3, 0, 0, 3),
+    (
+      summary.success_count,
+      summary.failure_count,
+      summary.other_count,
+      summary.processed_files
+    )
+  );
+  assert_eq!(
+    vec![dir.tag(), library_tag.clone(), tags[0].clone(), tags[1].clone()],
+    requested_tags(&server, "deploy")
+  );
+  assert_eq!(
+    vec![dir.tag(), tags[0].clone(), tags[1].clone(), library_tag.clone()],
+    requested_tags(&server, "undeploy")
+  );
+  let events = collector.events.borrow();
+  let position = |expected: &Event| events.iter().position(|event| event == expected).unwrap();
+  assert!(
+    position(&Event::FileFinished(dir.file("c/0001-input-data-string-test-01.xml")))
+      < position(&Event::ModelUndeployed(library_tag))
+  );
+}
+
+#[test]
+fn test_import_not_deployed_across_directories() {
+  let dir = TestDir::new("import-not-deployed-across-directories");
+  let server = server(&dir);
+  let (tags, library_tag) = import_across_directories(&dir, &server);
+  server.inject("deploy", &library_tag, Fault::Error("invalid model".to_string()));
+  let config = ConfigurationParams {
+    continue_on_deployment_failure: true,
+    ..config(&server, &dir.path.display().to_string(), 5000)
+  };
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let summary = runner.run().unwrap();
+  assert_eq!(
+    (1, 0, 2),
+    (summary.success_count, summary.failure_count, summary.other_count)
+  );
+  assert_eq!(
+    Some("invalid model"),
+    summary.deployment_errors.get(&library_tag).map(String::as_str)
+  );
+  for tag in &tags {
+    assert_eq!(
+      Some("imported model 'library.dmn' was not deployed"),
+      summary.deployment_errors.get(tag).map(String::as_str)
+    );
+  }
+  assert_eq!(vec![dir.tag(), library_tag], requested_tags(&server, "deploy"));
+}
+
+#[test]
+fn test_model_parsing_failed() {
+  let dir = TestDir::new("model-parsing-failed");
+  fs::write(dir.file("unused.dmn"), "<definitions").unwrap();
+  let server = server(&dir);
+  let config = config(&server, &dir.path.display().to_string(), 5000);
+  let summary = Runner::with_engine(config.clone(), Box::new(engine(&server, 5000)))
+    .run()
+    .unwrap();
+  assert_eq!((1, 0), (summary.success_count, summary.other_count));
+  fs::write(dir.file(MODEL_NAME), "<definitions").unwrap();
+  let config = ConfigurationParams {
+    continue_on_deployment_failure: true,
+    ..config
+  };
+  let summary = Runner::with_engine(config, Box::new(engine(&server, 5000)))
+    .run()
+    .unwrap();
+  assert_eq!((0, 1), (summary.success_count, summary.other_count));
+  assert!(summary.deployment_errors[&dir.tag()].starts_with("ParsingXMLFailed"));
+  assert_eq!(1, server.requests("deploy").len());
+}
+
+#[test]
+fn test_imported_model_parsing_failed() {
+  let dir = TestDir::new("imported-model-parsing-failed");
+  let server = server(&dir);
+  let (tags, library_tag) = import_across_directories(&dir, &server);
+  fs::write(dir.path.join("b").join("library.dmn"), "<definitions").unwrap();
+  fs::remove_dir_all(dir.path.join("c")).unwrap();
+  let other_dir = dir.path.join("d");
+  fs::create_dir_all(&other_dir).unwrap();
+  fs::write(other_dir.join(MODEL_NAME), MODEL_CONTENT).unwrap();
+  fs::write(other_dir.join("0001-input-data-string-test-01.xml"), TC_0001).unwrap();
+  let other_tag = model_tag(&dir.path, &dir.file("d"), MODEL_NAME);
+  server.answer_test_cases(&other_tag, &parse_from_string(TC_0001).unwrap());
+  let config = config(&server, &dir.path.display().to_string(), 5000);
+  let result = Runner::with_engine(config.clone(), Box::new(engine(&server, 5000))).run();
+  assert!(matches!(result, Err(RunnerError::DeploymentFailed(_))));
+  let config = ConfigurationParams {
+    continue_on_deployment_failure: true,
+    ..config
+  };
+  let summary = Runner::with_engine(config, Box::new(engine(&server, 5000)))
+    .run()
+    .unwrap();
+  assert_eq!(
+    (2, 0, 1, 3),
+    (
+      summary.success_count,
+      summary.failure_count,
+      summary.other_count,
+      summary.processed_files
+    )
+  );
+  assert!(summary.deployment_errors[&library_tag].starts_with("ParsingXMLFailed"));
+  assert!(summary.deployment_errors[&tags[0]].starts_with("ImportNotFound"));
+  let deployed = requested_tags(&server, "deploy");
+  assert!(!deployed.contains(&tags[0]) && !deployed.contains(&library_tag));
+  assert_eq!(Some(&other_tag), deployed.last());
+}
+
+#[test]
+fn test_run_aborted_on_error() {
+  let dir = TestDir::new("run-aborted-on-error");
+  let server = server(&dir);
+  server.inject("deploy", &dir.tag(), Fault::Error("invalid model".to_string()));
+  let collector = Collector::default();
+  let mut runner = Runner::with_engine(
+    config(&server, &dir.path.display().to_string(), 5000),
+    Box::new(engine(&server, 5000)),
+  );
+  runner.add_listener(Box::new(collector.clone()));
+  assert!(matches!(runner.run(), Err(RunnerError::DeploymentFailed(_))));
+  let events = collector.events.borrow();
+  let n = events.len();
+  assert!(n >= 2);
+  assert_eq!(
+    Event::RunAborted("DeploymentFailed(\"invalid model\")".to_string()),
+    events[n - 2]
+  );
+  match &events[n - 1] {
+    Event::RunFinished(summary) => {
+      assert!(summary.cancelled);
+      assert_eq!(0, summary.processed_files);
+      assert_eq!(
+        Some("DeploymentFailed(\"invalid model\")".to_string()),
+        summary.stop_reason
+      );
+    }
+    event => panic!("unexpected event {:?}", event),
+  }
+}
+
+#[test]
+fn test_invalid_file_name_pattern() {
+  let dir = TestDir::new("invalid-file-name-pattern");
+  let server = server(&dir);
+  let config = ConfigurationParams {
+    file_name_pattern: "0001-(".to_string(),
+    ..config(&server, &dir.path.display().to_string(), 5000)
+  };
+  let result = Runner::with_engine(config, Box::new(engine(&server, 5000))).run();
+  assert!(
+    matches!(result, Err(RunnerError::InvalidConfiguration(reason)) if reason.starts_with("invalid matching pattern '0001-('"))
+  );
+  assert!(server.requests("deploy").is_empty());
+}
+
+#[test]
+fn test_stop_on_failure() {
+  let dir = TestDir::new("stop-on-failure");
+  fs::write(dir.file("0001-input-data-string-test-02.xml"), TC_0001).unwrap();
+  let server = server(&dir);
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  let mut runner = Runner::with_engine(config.clone(), Box::new(engine(&server, 5000)));
+  let summary = runner.run().unwrap();
+  assert_eq!(
+    (2, 0, 2, false),
+    (
+      summary.success_count,
+      summary.failure_count,
+      summary.processed_files,
+      summary.cancelled
+    )
+  );
+  server.inject(
+    "evaluate",
+    DECISION_NAME,
+    Fault::Error(r#"division by "zero""#.to_string()),
+  );
+  config.stop_on_failure = true;
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let report_file = dir.file("report.csv");
+  runner.add_listener(Box::new(CsvReport::new(&report_file).unwrap()));
+  let deployment_report_file = dir.file("deployments.csv");
+  runner.add_listener(Box::new(DeploymentReport::new(&deployment_report_file).unwrap()));
+  let collector = Collector::default();
+  runner.add_listener(Box::new(collector.clone()));
+  let summary = runner.run().unwrap();
+  assert_eq!(
+    (0, 1, 1, true),
+    (
+      summary.success_count,
+      summary.failure_count,
+      summary.processed_files,
+      summary.cancelled
+    )
+  );
+  let events = collector.events.borrow();
+  assert_eq!(Event::ModelUndeployed(dir.tag()), events[events.len() - 2]);
+  assert_eq!(Event::RunFinished(summary), events[events.len() - 1]);
+  let report: Vec<String> = fs::read_to_string(report_file)
+    .unwrap()
+    .lines()
+    .map(|line| line.to_string())
+    .collect();
+  assert_eq!(1, report.len());
+  assert!(report[0].starts_with(&format!(
+    r#""{}","0001-input-data-string-test-01.xml","#,
+    dir.path.display()
+  )));
+  assert!(report[0].contains(r#""001","FAILURE","division by ""zero""",""#));
+  let deployment_report = fs::read_to_string(deployment_report_file).unwrap();
+  assert_eq!(1, deployment_report.lines().count());
+  assert!(deployment_report.starts_with(&format!(r#""{}","{}","model-"#, dir.path.display(), MODEL_NAME)));
+}
+
+#[test]
+fn test_stop_file_on_failure() {
+  let dir = TestDir::new("stop-file-on-failure");
+  let two_tests = two_tests();
+  fs::write(dir.file("0001-input-data-string-test-01.xml"), &two_tests).unwrap();
+  fs::write(dir.file("0001-input-data-string-test-02.xml"), &two_tests).unwrap();
+  let server = server(&dir);
+  server.inject("evaluate", DECISION_NAME, Fault::Error("division by zero".to_string()));
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.fail_fast.stop_file_on_failure = true;
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let collector = Collector::default();
+  runner.add_listener(Box::new(collector.clone()));
+  let summary = runner.run().unwrap();
+  assert_eq!(
+    (0, 2, 2, 2, false),
+    (
+      summary.success_count,
+      summary.failure_count,
+      summary.other_count,
+      summary.processed_files,
+      summary.cancelled
+    )
+  );
+  let statuses = statuses(&collector.events.borrow());
+  let expected = vec![
+    ("001".to_string(), TestStatus::Failure),
+    ("002".to_string(), TestStatus::Skipped),
+    ("001".to_string(), TestStatus::Failure),
+    ("002".to_string(), TestStatus::Skipped),
+  ];
+  assert_eq!(expected, statuses);
+  assert_eq!(2, server.requests("evaluate").len());
+}
+
+#[test]
+fn test_stop_reason() {
+  let outcome = |status: TestStatus, transport_failed: bool| TestOutcome {
+    file_name: "test.xml".to_string(),
+    test_id: "001".to_string(),
+    result_name: "result".to_string(),
+    artifact: "decision".to_string(),
+    invoked: "result".to_string(),
+    status,
+    remarks: "connection refused".to_string(),
+    transport_failed,
+    duration: None,
+    actual: None,
+    expected: None,
+  };
+  let summary = |success_count: u64, failure_count: u64| RunSummary {
+    success_count,
+    failure_count,
+    ..Default::default()
+  };
+  let fail_fast = FailFastParams {
+    max_failures: Some(3),
+    max_failure_rate: Some(50.0),
+    min_tests_for_failure_rate: 10,
+    stop_on_transport_error: true,
+    ..Default::default()
+  };
+  assert_eq!(
+    None,
+    stop_reason(&fail_fast, &summary(1, 2), &outcome(TestStatus::Failure, false))
+  );
+  assert_eq!(
+    Some("3 failed tests".to_string()),
+    stop_reason(&fail_fast, &summary(1, 3), &outcome(TestStatus::Failure, false))
+  );
+  assert_eq!(
+    Some("engine not reachable: connection refused".to_string()),
+    stop_reason(&fail_fast, &summary(0, 1), &outcome(TestStatus::Failure, true))
+  );
+  let fail_fast = FailFastParams {
+    max_failure_rate: Some(50.0),
+    min_tests_for_failure_rate: 10,
+    ..Default::default()
+  };
+  assert_eq!(
+    None,
+    stop_reason(&fail_fast, &summary(3, 6), &outcome(TestStatus::Failure, true))
+  );
+  assert_eq!(
+    None,
+    stop_reason(&fail_fast, &summary(5, 5), &outcome(TestStatus::Failure, false))
+  );
+  assert_eq!(
+    Some("failure rate 60.0% exceeded 50.0% after 10 tests".to_string()),
+    stop_reason(&fail_fast, &summary(4, 6), &outcome(TestStatus::Failure, false))
+  );
+}
+
+#[test]
+fn test_wait_until_ready() {
+  let dir = TestDir::new("wait-until-ready");
+  let server = server(&dir);
+  server.inject("health", "", Fault::Status(503));
+  let mut engine = engine(&server, 5000);
+  let mut collector = Collector::default();
+  let not_healthy = || RunnerError::TransportFailed("engine is not healthy: 503 Service Unavailable".to_string());
+  assert_eq!(
+    Err(not_healthy()),
+    wait_until_ready(
+      &mut engine,
+      Duration::from_millis(200),
+      Duration::from_millis(50),
+      &mut collector
+    )
+  );
+  assert_eq!(
+    vec![Event::EngineUnavailable(format!("{:?}", not_healthy()))],
+    *collector.events.borrow()
+  );
+  collector.events.borrow_mut().clear();
+  thread::scope(|scope| {
+    scope.spawn(|| {
+      thread::sleep(Duration::from_millis(300));
+      server.clear("health", "");
+    });
+    assert_eq!(
+      Ok(()),
+      wait_until_ready(
+        &mut engine,
+        Duration::from_millis(5000),
+        Duration::from_millis(50),
+        &mut collector
+      )
+    );
+  });
+  assert_eq!(
+    vec![
+      Event::EngineUnavailable(format!("{:?}", not_healthy())),
+      Event::EngineAvailable
+    ],
+    *collector.events.borrow()
+  );
+  assert!(server.requests("health").len() > 2);
+}
+
+#[test]
+fn test_engine_not_ready() {
+  let dir = TestDir::new("engine-not-ready");
+  let server = server(&dir);
+  server.inject("health", "", Fault::Status(503));
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.health.ready_timeout_ms = Some(200);
+  config.health.check_interval_ms = Some(50);
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  assert_eq!(
+    Err(RunnerError::TransportFailed(
+      "engine is not healthy: 503 Service Unavailable".to_string()
+    )),
+    runner.run()
+  );
+  assert!(server.requests("deploy").is_empty());
+}
+
+#[test]
+fn test_engine_down_abort() {
+  let dir = TestDir::new("engine-down-abort");
+  fs::write(dir.file("0001-input-data-string-test-01.xml"), two_tests()).unwrap();
+  fs::write(dir.file("0001-input-data-string-test-02.xml"), two_tests()).unwrap();
+  let server = server(&dir);
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.health.on_engine_down = EngineDownAction::Abort;
+  let engine = EngineGoingDown {
+    engine: engine(&server, 5000),
+    down: false,
+  };
+  let mut runner = Runner::with_engine(config, Box::new(engine));
+  let collector = Collector::default();
+  runner.add_listener(Box::new(collector.clone()));
+  let summary = runner.run().unwrap();
+  assert_eq!(
+    (0, 1, 1, true),
+    (
+      summary.success_count,
+      summary.failure_count,
+      summary.processed_files,
+      summary.cancelled
+    )
+  );
+  assert_eq!(
+    Some(r#"engine not available: TransportFailed("connection refused")"#),
+    summary.stop_reason.as_deref()
+  );
+  let events = collector.events.borrow();
+  assert_eq!(vec![("001".to_string(), TestStatus::Failure)], statuses(&events));
+  assert!(events.contains(&Event::EngineUnavailable(
+    r#"TransportFailed("connection refused")"#.to_string()
+  )));
+  assert!(events.contains(&Event::ModelUndeployed(dir.tag())));
+  assert!(server.requests("evaluate").is_empty());
+}
+
+#[test]
+fn test_engine_down_pause() {
+  let dir = TestDir::new("engine-down-pause");
+  let server = server(&dir);
+  let mut engine = engine(&server, 200);
+  deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine).unwrap();
+  server.inject("evaluate", DECISION_NAME, Fault::Delay(Duration::from_millis(1000)));
+  server.inject("health", "", Fault::Status(503));
+  let options = ExecutionOptions {
+    on_engine_down: EngineDownAction::Pause,
+    ready_timeout: Duration::from_millis(5000),
+    check_interval: Duration::from_millis(50),
+    ..Default::default()
+  };
+  let file_name = dir.file("0001-input-data-string-test-01.xml");
+  let mut collector = Collector::default();
+  thread::scope(|scope| {
+    scope.spawn(|| {
+      thread::sleep(Duration::from_millis(600));
+      server.clear("evaluate", DECISION_NAME);
+      server.clear("health", "");
+    });
+    execute_tests(
+      &TestFile::read(&file_name).unwrap(),
+      &dir.path,
+      &mut engine,
+      &options,
+      &Deployments::default(),
+      &mut collector,
+      &CancellationToken::default(),
+    )
+    .unwrap();
+  });
+  let events = collector.events.borrow();
+  assert!(matches!(&events[1], Event::EngineUnavailable(_)));
+  assert_eq!(Event::EngineAvailable, events[2]);
+  assert_eq!(vec![("001".to_string(), TestStatus::Success)], statuses(&events));
+  assert_eq!(Event::FileFinished(file_name), events[4]);
+  assert_eq!(2, server.requests("evaluate").len());
+}
+
+#[test]
+fn test_engine_down_pause_redeploy() {
+  let dir = TestDir::new("engine-down-pause-redeploy");
+  let server = server(&dir);
+  deploy_dmn_definitions(&dir.file(MODEL_NAME), &dir.tag(), &mut engine(&server, 200)).unwrap();
+  server.inject("evaluate", DECISION_NAME, Fault::Delay(Duration::from_millis(1000)));
+  server.inject("health", "", Fault::Status(503));
+  let options = ExecutionOptions {
+    on_engine_down: EngineDownAction::Pause,
+    ready_timeout: Duration::from_millis(5000),
+    check_interval: Duration::from_millis(50),
+    ..Default::default()
+  };
+  let mut deployments = Deployments::default();
+  deployments.models.push((dir.tag(), dir.file(MODEL_NAME)));
+  let test_file = TestFile::read(&dir.file("0001-input-data-string-test-01.xml")).unwrap();
+  let execute = |deployments: &Deployments| {
+    let mut collector = Collector::default();
+    thread::scope(|scope| {
+      scope.spawn(|| {
+        thread::sleep(Duration::from_millis(600));
+        server.drop_deployments();
+        server.clear("evaluate", DECISION_NAME);
+        server.clear("health", "");
+      });
+      execute_tests(
+        &test_file,
+        &dir.path,
+        &mut engine(&server, 200),
+        &options,
+        deployments,
+        &mut collector,
+        &CancellationToken::default(),
+      )
+      .unwrap();
+    });
+    let events = collector.events.borrow().clone();
+    events
+  };
+  let events = execute(&deployments);
+  assert_eq!(vec![("001".to_string(), TestStatus::Success)], statuses(&events));
+  assert_eq!(2, server.requests("deploy").len());
+  server.inject("evaluate", DECISION_NAME, Fault::Delay(Duration::from_millis(1000)));
+  server.inject("health", "", Fault::Status(503));
+  server.inject("deploy", &dir.tag(), Fault::Error("invalid model".to_string()));
+  let events = execute(&deployments);
+  assert_eq!(vec![("001".to_string(), TestStatus::Failure)], statuses(&events));
+  assert!(events.contains(&Event::RunAborted(
+    r#"redeploying models failed: DeploymentFailed("invalid model")"#.to_string()
+  )));
+}
+
+/// Engine going down on the first evaluation.
+struct EngineGoingDown {
+  engine: RestEngine,
+  down: bool,
+}
+
+impl Engine for EngineGoingDown {
+  fn deploy(&mut self, params: &DeployParams) -> crate::errors::Result<ResultDto<DeployResult>> {
+    self.engine.deploy(params)
+  }
+
+  fn evaluate(&mut self, _params: &EvaluateParams) -> crate::errors::Result<ResultDto<ActualValueDto>> {
+    self.down = true;
+    Err(RunnerError::TransportFailed("connection refused".to_string()))
+  }
+
+  fn undeploy(&mut self, params: &UndeployParams) -> crate::errors::Result<ResultDto<UndeployResult>> {
+    self.engine.undeploy(params)
+  }
+
+  fn verify(&mut self, params: &VerifyParams) -> crate::errors::Result<bool> {
+    self.engine.verify(params)
+  }
+
+  fn health(&mut self) -> crate::errors::Result<()> {
+    if self.down {
+      Err(RunnerError::TransportFailed("connection refused".to_string()))
+    } else {
+      self.engine.health()
+    }
+  }
+}
+
+#[test]
+fn test_benchmark() {
+  let dir = TestDir::new("benchmark");
+  let server = server(&dir);
+  let report_file = dir.file("benchmark.json");
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.benchmark = Some(BenchmarkParams {
+    warmup_iterations: 2,
+    iterations: 10,
+    concurrency: 3,
+    report_file_path: report_file.clone(),
+    report_format: BenchmarkFormat::Json,
+  });
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  runner.add_listener(Box::new(
+    BenchmarkReport::new(&report_file, BenchmarkFormat::Json).unwrap(),
+  ));
+  let summary = runner.benchmark().unwrap();
+  assert_eq!(
+    (1, 0, 1),
+    (summary.success_count, summary.failure_count, summary.processed_files)
+  );
+  assert_eq!(12, server.requests("evaluate").len());
+  let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&report_file).unwrap()).unwrap();
+  let test = &report["tests"][0];
+  assert_eq!("001", test["id"]);
+  assert_eq!(DECISION_NAME, test["result"]);
+  assert_eq!(10, test["count"]);
+  assert_eq!(0, test["errors"]);
+  assert!(test["minMs"].as_f64().unwrap() <= test["p95Ms"].as_f64().unwrap());
+  assert!(test["throughput"].as_f64().unwrap() > 0.0);
+  let labels = report["labels"].as_array().unwrap();
+  assert_eq!(4, labels.len());
+  assert_eq!("Compliance Level 2", labels[0]["label"]);
+  assert_eq!(10, labels[0]["count"]);
+}
+
+#[test]
+fn test_benchmark_unexpected_value() {
+  let dir = TestDir::new("benchmark-unexpected-value");
+  let server = server(&dir);
+  server.inject(
+    "evaluate",
+    DECISION_NAME,
+    Fault::Response(serde_json::json!({ "data": { "value": { "type": "xsd:string", "text": "Hello" } } })),
+  );
+  let report_file = dir.file("benchmark.json");
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.benchmark = Some(BenchmarkParams {
+    warmup_iterations: 0,
+    iterations: 4,
+    concurrency: 1,
+    report_file_path: report_file.clone(),
+    report_format: BenchmarkFormat::Json,
+  });
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  runner.add_listener(Box::new(
+    BenchmarkReport::new(&report_file, BenchmarkFormat::Json).unwrap(),
+  ));
+  let summary = runner.benchmark().unwrap();
+  assert_eq!((0, 1), (summary.success_count, summary.failure_count));
+  let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&report_file).unwrap()).unwrap();
+  assert_eq!(0, report["tests"][0]["count"]);
+  assert_eq!(4, report["tests"][0]["errors"]);
+}
+
+#[test]
+fn test_benchmark_csv() {
+  let dir = TestDir::new("benchmark-csv");
+  let server = server(&dir);
+  server.inject("evaluate", DECISION_NAME, Fault::Error("division by zero".to_string()));
+  let report_file = dir.file("benchmark.csv");
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.benchmark = Some(BenchmarkParams {
+    warmup_iterations: 0,
+    iterations: 3,
+    concurrency: 1,
+    report_file_path: report_file.clone(),
+    report_format: BenchmarkFormat::Csv,
+  });
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  runner.add_listener(Box::new(
+    BenchmarkReport::new(&report_file, BenchmarkFormat::Csv).unwrap(),
+  ));
+  let summary = runner.benchmark().unwrap();
+  assert_eq!((0, 1), (summary.success_count, summary.failure_count));
+  let report = fs::read_to_string(&report_file).unwrap();
+  let lines: Vec<&str> = report.lines().collect();
+  assert_eq!(6, lines.len());
+  assert!(lines[0].starts_with(r#""scope","dir","file","id","result","label","count","errors","min_ms""#));
+  assert!(lines[1].starts_with(r#""test","#));
+  assert!(lines[1].contains(r#""0001-input-data-string-test-01.xml","001","Greeting Message","Compliance Level 2;"#));
+  assert!(lines[1].contains(r#""0","3","","","","","","#));
+  assert!(lines[2].starts_with(r#""label","","","","","Compliance Level 2","0","3","#));
+}
+
+#[test]
+fn test_slowest_tests() {
+  let dir = TestDir::new("slowest-tests");
+  fs::write(dir.file("0001-input-data-string-test-01.xml"), two_tests()).unwrap();
+  fs::write(dir.file("0001-input-data-string-test-02.xml"), two_tests()).unwrap();
+  let server = server(&dir);
+  let config = config(&server, &dir.path.display().to_string(), 5000);
+  let mut runner = Runner::with_engine(
+    ConfigurationParams {
+      slowest_tests: 3,
+      ..config.clone()
+    },
+    Box::new(engine(&server, 5000)),
+  );
+  let summary = runner.run().unwrap();
+  assert_eq!(4, summary.success_count);
+  assert_eq!(3, summary.slowest_tests.len());
+  assert!(summary
+    .slowest_tests
+    .windows(2)
+    .all(|timings| timings[0].duration >= timings[1].duration));
+  let mut runner = Runner::with_engine(
+    ConfigurationParams {
+      slowest_tests: 0,
+      ..config
+    },
+    Box::new(engine(&server, 5000)),
+  );
+  assert!(runner.run().unwrap().slowest_tests.is_empty());
+}
+
+/// Listener cancelling the run after the first evaluated test.
+struct Interrupter {
+  cancellation: CancellationToken,
+}
+
+impl Listener for Interrupter {
+  fn on_event(&mut self, event: &Event) -> crate::errors::Result<()> {
+    if let Event::TestEvaluated(_) = event {
+      self.cancellation.cancel();
+    }
+    Ok(())
+  }
+}
+
+#[test]
+fn test_resume_from_checkpoint() {
+  let dir = TestDir::new("resume");
+  fs::write(dir.file("0001-input-data-string-test-01.xml"), two_tests()).unwrap();
+  fs::write(dir.file("0001-input-data-string-test-02.xml"), two_tests()).unwrap();
+  let server = server(&dir);
+  let checkpoint_file = dir.file("checkpoint.jsonl");
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.checkpoint_file_path = Some(checkpoint_file.clone());
+  // interrupted run
+  let mut runner = Runner::with_engine(config.clone(), Box::new(engine(&server, 5000)));
+  let cancellation = runner.cancellation_token();
+  runner.add_listener(Box::new(Interrupter { cancellation }));
+  let summary = runner.run().unwrap();
+  assert!(summary.cancelled);
+  assert_eq!(1, summary.success_count);
+  // line written partially when the runner was terminated
+  let mut content = fs::read_to_string(&checkpoint_file).unwrap();
+  content.push_str(r#"{"fileName":"#);
+  fs::write(&checkpoint_file, content).unwrap();
+  assert_eq!(1, Checkpoint::load(&checkpoint_file).unwrap().len());
+  // resumed run
+  let report_file = dir.file("report.csv");
+  let mut runner = Runner::with_engine(config.clone(), Box::new(engine(&server, 5000)));
+  runner.add_listener(Box::new(CsvReport::new(&report_file).unwrap()));
+  let summary = runner.resume().unwrap();
+  assert_eq!(
+    (4, 0, false),
+    (summary.success_count, summary.failure_count, summary.cancelled)
+  );
+  assert_eq!(4, server.requests("evaluate").len());
+  assert_eq!(2, server.requests("deploy").len());
+  assert_eq!(4, fs::read_to_string(&report_file).unwrap().lines().count());
+  let checkpoint = Checkpoint::load(&checkpoint_file).unwrap();
+  assert_eq!(4, checkpoint.len());
+  assert!(checkpoint.is_completed(&TestFile::read(&dir.file("0001-input-data-string-test-02.xml")).unwrap()));
+  // resuming the completed run does not deploy models nor evaluate tests
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let summary = runner.resume().unwrap();
+  assert_eq!(4, summary.success_count);
+  assert_eq!(4, server.requests("evaluate").len());
+  assert_eq!(2, server.requests("deploy").len());
+}
+
+#[test]
+fn test_resume_deploys_models_of_incomplete_files() {
+  let dir = TestDir::new("resume-incomplete-files");
+  let other_model = "0002-input-data-string.dmn";
+  let other_file = dir.file("0002-input-data-string-test-01.xml");
+  let other_tests = two_tests()
+    .replace(MODEL_NAME, other_model)
+    .replace(r#" id="001""#, "")
+    .replace(r#" id="002""#, "");
+  fs::write(dir.file(other_model), LIBRARY_MODEL_CONTENT).unwrap();
+  fs::write(&other_file, &other_tests).unwrap();
+  let other_tag = model_tag(&dir.path, &dir.path.display().to_string(), other_model);
+  let server = server(&dir);
+  server.answer_test_cases(&other_tag, &parse_from_string(&other_tests).unwrap());
+  let checkpoint_file = dir.file("checkpoint.jsonl");
+  let mut config = config(&server, &dir.path.display().to_string(), 5000);
+  config.checkpoint_file_path = Some(checkpoint_file.clone());
+  // interrupted run completes the first test file
+  let mut runner = Runner::with_engine(config.clone(), Box::new(engine(&server, 5000)));
+  let cancellation = runner.cancellation_token();
+  runner.add_listener(Box::new(Interrupter { cancellation }));
+  assert_eq!(1, runner.run().unwrap().success_count);
+  let deployed = requested_tags(&server, "deploy").len();
+  // resumed run deploys only the model of the test file that was not completed
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 5000)));
+  let collector = Collector::default();
+  runner.add_listener(Box::new(collector.clone()));
+  let summary = runner.resume().unwrap();
+  assert_eq!((3, 0), (summary.success_count, summary.failure_count));
+  assert_eq!(vec![other_tag], requested_tags(&server, "deploy")[deployed..].to_vec());
+  assert_eq!(
+    vec![
+      ("001".to_string(), TestStatus::Success),
+      ("#1".to_string(), TestStatus::Success),
+      ("#2".to_string(), TestStatus::Success)
+    ],
+    statuses(&collector.events.borrow())
+  );
+  // tests without identifiers are saved in the checkpoint separately
+  let checkpoint = Checkpoint::load(&checkpoint_file).unwrap();
+  assert_eq!(3, checkpoint.len());
+  assert!(checkpoint.is_completed(&TestFile::read(&other_file).unwrap()));
+}
+
+#[test]
+fn test_checkpoint_incomplete_tests() {
+  let dir = TestDir::new("checkpoint-incomplete");
+  let server = server(&dir);
+  server.inject("evaluate", DECISION_NAME, Fault::Delay(Duration::from_millis(1000)));
+  let checkpoint_file = dir.file("checkpoint.jsonl");
+  let mut config = config(&server, &dir.path.display().to_string(), 200);
+  config.checkpoint_file_path = Some(checkpoint_file.clone());
+  let mut runner = Runner::with_engine(config.clone(), Box::new(engine(&server, 200)));
+  assert_eq!(1, runner.run().unwrap().failure_count);
+  // tests that failed on transport are written but not completed
+  assert_eq!(1, fs::read_to_string(&checkpoint_file).unwrap().lines().count());
+  assert!(Checkpoint::load(&checkpoint_file).unwrap().is_empty());
+  server.clear("evaluate", DECISION_NAME);
+  let mut runner = Runner::with_engine(config.clone(), Box::new(engine(&server, 200)));
+  assert_eq!(1, runner.resume().unwrap().success_count);
+  assert_eq!(1, Checkpoint::load(&checkpoint_file).unwrap().len());
+  config.checkpoint_file_path = None;
+  let mut runner = Runner::with_engine(config, Box::new(engine(&server, 200)));
+  assert_eq!(
+    Err(RunnerError::InvalidConfiguration(
+      "checkpoint file not specified".to_string()
+    )),
+    runner.resume()
+  );
+}
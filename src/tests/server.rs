@@ -0,0 +1,281 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Mock DMN™ engine server for testing the runner without a live engine.
+//!
+//! The server implements `/deploy`, `/evaluate`, `/undeploy`, `/verify` and `/health` endpoints
+//! of the default REST API. Evaluation results are answered from expected values
+//! of test cases, faults (errors, delays, malformed responses, error statuses) can be injected
+//! for selected operations.
+
+use crate::dto::ValueDto;
+use crate::model::TestCases;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Fault injected into the response of the mock server.
+#[derive(Debug, Clone)]
+pub enum Fault {
+  /// Responds with the error reported by the engine.
+  Error(String),
+  /// Delays the response.
+  Delay(Duration),
+  /// Responds with the text that is not a valid JSON.
+  Malformed,
+  /// Responds with the scripted JSON response.
+  Response(Value),
+  /// Responds with specified HTTP status and empty JSON object.
+  Status(u16),
+}
+
+/// State shared between the server and request handlers.
+#[derive(Default)]
+struct State {
+  /// Expected values indexed by the key built from evaluation parameters.
+  answers: HashMap<String, Value>,
+  /// Faults indexed by operation and the name (for evaluation) or tag (for other operations).
+  faults: HashMap<(String, String), Fault>,
+  /// Tags of deployed models.
+  deployed: BTreeSet<String>,
+  /// Tags of models imported by the model with the tag used as a key.
+  imports: HashMap<String, Vec<String>>,
+  /// Number of models deployed so far, used to generate identifiers.
+  deployments: usize,
+  /// All requests received so far, as pairs of operation and request body.
+  requests: Vec<(String, Value)>,
+}
+
+/// Mock engine server listening on a random local port, stopped when dropped.
+pub struct MockServer {
+  address: SocketAddr,
+  state: Arc<Mutex<State>>,
+  stopped: Arc<AtomicBool>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+  /// Starts the server.
+  pub fn start() -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let state = Arc::new(Mutex::new(State::default()));
+    let stopped = Arc::new(AtomicBool::new(false));
+    let handle = {
+      let state = state.clone();
+      let stopped = stopped.clone();
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          if stopped.load(Ordering::SeqCst) {
+            break;
+          }
+          if let Ok(stream) = stream {
+            let state = state.clone();
+            thread::spawn(move || handle_connection(stream, &state));
+          }
+        }
+      })
+    };
+    Self {
+      address,
+      state,
+      stopped,
+      handle: Some(handle),
+    }
+  }
+
+  /// Returns the URL of the endpoint for specified operation.
+  pub fn url(&self, operation: &str) -> String {
+    format!("http://{}/{}", self.address, operation)
+  }
+
+  /// Registers expected values of all test cases as answers for the model with specified tag.
+  pub fn answer_test_cases(&self, tag: &str, test_cases: &TestCases) {
+    let mut state = self.state.lock().unwrap();
+    for test_case in &test_cases.test_cases {
+      for result_node in &test_case.result_nodes {
+        let params = serde_json::to_value(crate::runner::evaluate_params(
+          &Some(tag.to_string()),
+          test_case,
+          result_node,
+        ))
+        .unwrap();
+        let value = result_node.expected.as_ref().map(ValueDto::from);
+        state.answers.insert(answer_key(&params), json!({ "value": value }));
+      }
+    }
+  }
+
+  /// Declares that the model with specified tag imports another model,
+  /// the model can be evaluated only when the imported model is deployed.
+  pub fn import(&self, tag: &str, imported_tag: &str) {
+    let mut state = self.state.lock().unwrap();
+    state
+      .imports
+      .entry(tag.to_string())
+      .or_default()
+      .push(imported_tag.to_string());
+  }
+
+  /// Injects the fault into responses for specified operation and name (or tag).
+  pub fn inject(&self, operation: &str, key: &str, fault: Fault) {
+    let mut state = self.state.lock().unwrap();
+    state.faults.insert((operation.to_string(), key.to_string()), fault);
+  }
+
+  /// Removes the fault injected for specified operation and name (or tag).
+  pub fn clear(&self, operation: &str, key: &str) {
+    let mut state = self.state.lock().unwrap();
+    state.faults.remove(&(operation.to_string(), key.to_string()));
+  }
+
+  /// Removes all deployed models, like the engine restarted without persisting deployments.
+  pub fn drop_deployments(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.deployed.clear();
+  }
+
+  /// Returns all requests received so far for specified operation.
+  pub fn requests(&self, operation: &str) -> Vec<Value> {
+    let state = self.state.lock().unwrap();
+    state
+      .requests
+      .iter()
+      .filter(|(op, _)| op == operation)
+      .map(|(_, body)| body.clone())
+      .collect()
+  }
+}
+
+impl Drop for MockServer {
+  /// Stops the server, the listener is woken up with a dummy connection.
+  fn drop(&mut self) {
+    self.stopped.store(true, Ordering::SeqCst);
+    let _ = TcpStream::connect(self.address);
+    if let Some(handle) = self.handle.take() {
+      let _ = handle.join();
+    }
+  }
+}
+
+/// Builds the key identifying evaluation parameters.
+fn answer_key(params: &Value) -> String {
+  json!([params["tag"], params["name"], params["resultName"], params["input"]]).to_string()
+}
+
+/// Reads a single HTTP request and writes the response.
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) {
+  let mut reader = BufReader::new(stream);
+  let mut request_line = String::new();
+  if reader.read_line(&mut request_line).is_err() || request_line.is_empty() {
+    return;
+  }
+  let operation = request_line
+    .split_whitespace()
+    .nth(1)
+    .unwrap_or_default()
+    .trim_start_matches('/')
+    .to_string();
+  let mut content_length = 0;
+  loop {
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+      break;
+    }
+    if let Some((name, value)) = line.split_once(':') {
+      if name.eq_ignore_ascii_case("content-length") {
+        content_length = value.trim().parse().unwrap_or(0);
+      }
+    }
+  }
+  let mut body = vec![0; content_length];
+  if reader.read_exact(&mut body).is_err() {
+    return;
+  }
+  let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+  let (status, response) = respond(&operation, body, state);
+  let mut stream = reader.into_inner();
+  let _ = write!(
+    stream,
+    "HTTP/1.1 {} MOCK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    response.len(),
+    response
+  );
+  let _ = stream.flush();
+}
+
+/// Prepares the response status and body for specified operation.
+fn respond(operation: &str, body: Value, state: &Mutex<State>) -> (u16, String) {
+  let tag = body["tag"].as_str().unwrap_or_default().to_string();
+  let key = if operation == "evaluate" {
+    body["name"].as_str().unwrap_or_default().to_string()
+  } else {
+    tag.clone()
+  };
+  let fault = {
+    let mut state = state.lock().unwrap();
+    state.requests.push((operation.to_string(), body.clone()));
+    state.faults.get(&(operation.to_string(), key)).cloned()
+  };
+  match fault {
+    Some(Fault::Error(details)) => return (200, json!({ "errors": [{ "details": details }] }).to_string()),
+    Some(Fault::Malformed) => return (200, r#"{"data": {"value": "#.to_string()),
+    Some(Fault::Response(response)) => return (200, response.to_string()),
+    Some(Fault::Status(status)) => return (status, "{}".to_string()),
+    Some(Fault::Delay(delay)) => thread::sleep(delay),
+    None => {}
+  }
+  let mut state = state.lock().unwrap();
+  let response = match operation {
+    "deploy" => {
+      state.deployments += 1;
+      state.deployed.insert(tag.clone());
+      json!({ "data": { "name": tag, "id": format!("model-{}", state.deployments), "tag": tag } })
+    }
+    "undeploy" => {
+      state.deployed.remove(&tag);
+      json!({ "data": { "tag": tag } })
+    }
+    "health" => json!({ "data": {} }),
+    "verify" if state.deployed.contains(&tag) => json!({ "data": { "tag": tag, "id": body["id"] } }),
+    "verify" => json!({ "errors": [{ "details": format!("model '{}' is not deployed", tag) }] }),
+    "evaluate" if !state.deployed.contains(&tag) => {
+      json!({ "errors": [{ "details": format!("model '{}' is not deployed", tag) }] })
+    }
+    "evaluate"
+      if !state
+        .imports
+        .get(&tag)
+        .into_iter()
+        .flatten()
+        .all(|i| state.deployed.contains(i)) =>
+    {
+      json!({ "errors": [{ "details": format!("model '{}' imports model that is not deployed", tag) }] })
+    }
+    "evaluate" => match state.answers.get(&answer_key(&body)) {
+      Some(answer) => json!({ "data": answer }),
+      None => json!({ "errors": [{ "details": "no answer for evaluation parameters" }] }),
+    },
+    _ => json!({ "errors": [{ "details": format!("unknown operation '{}'", operation) }] }),
+  };
+  (200, response.to_string())
+}
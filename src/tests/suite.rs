@@ -0,0 +1,76 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for suites stored in XML, JSON and YAML files.
+
+use crate::errors::RunnerError;
+use crate::model::parse_from_string;
+use crate::suite::{convert, read_from_file, read_from_string, write_to_string, SuiteFormat};
+use crate::tests::{TC_0001, TC_0001_YAML, TC_0002, TC_0003};
+
+#[test]
+fn test_format_from_file_name() {
+  assert_eq!(Ok(SuiteFormat::Xml), SuiteFormat::from_file_name("/a/0001-test-01.xml"));
+  assert_eq!(
+    Ok(SuiteFormat::Json),
+    SuiteFormat::from_file_name("/a/0001-test-01.tck.json")
+  );
+  assert_eq!(
+    Ok(SuiteFormat::Yaml),
+    SuiteFormat::from_file_name("/a/0001-test-01.tck.yaml")
+  );
+  assert_eq!(
+    Err(RunnerError::UnsupportedFileFormat("/a/config.yaml".to_string())),
+    SuiteFormat::from_file_name("/a/config.yaml")
+  );
+}
+
+#[test]
+fn test_read_yaml_0001() {
+  let expected = parse_from_string(TC_0001).unwrap();
+  let actual = read_from_string(TC_0001_YAML, SuiteFormat::Yaml).unwrap();
+  assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_round_trip_json_and_yaml() {
+  for content in &[TC_0001, TC_0002, TC_0003] {
+    let expected = parse_from_string(content).unwrap();
+    for format in &[SuiteFormat::Json, SuiteFormat::Yaml] {
+      let written = write_to_string(&expected, *format).unwrap();
+      let actual = read_from_string(&written, *format).unwrap();
+      assert_eq!(expected, actual);
+    }
+  }
+}
+
+#[test]
+fn test_convert() {
+  let dir = std::env::temp_dir().join(format!("dmn-tck-rs-suite-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let xml_file = dir.join("dmn-tck-rs-suite-0002.xml").display().to_string();
+  let json_file = dir.join("dmn-tck-rs-suite-0002.tck.json").display().to_string();
+  let yaml_file = dir.join("dmn-tck-rs-suite-0002.tck.yaml").display().to_string();
+  std::fs::write(&xml_file, TC_0002).unwrap();
+  convert(&xml_file, &json_file).unwrap();
+  convert(&json_file, &yaml_file).unwrap();
+  convert(&yaml_file, &xml_file).unwrap();
+  assert_eq!(parse_from_string(TC_0002).unwrap(), read_from_file(&xml_file).unwrap());
+  for file_name in &[xml_file, json_file, yaml_file] {
+    std::fs::remove_file(file_name).unwrap();
+  }
+  std::fs::remove_dir(&dir).unwrap();
+}
@@ -0,0 +1,115 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Test cases for XML writer.
+
+use crate::model::{parse_from_string, Component, List, ResultNode, Simple, TestCase, TestCaseType, TestCases, Value};
+use crate::tests::{TC_0001, TC_0002, TC_0003};
+use crate::validator::validate_test_cases_file;
+use crate::writer::{write_to_file, write_to_string};
+
+#[test]
+fn test_write_0001() {
+  let test_cases = parse_from_string(TC_0001).unwrap();
+  assert_eq!(TC_0001.replace("\r\n", "\n"), write_to_string(&test_cases).trim_end());
+}
+
+#[test]
+fn test_round_trip() {
+  for content in &[TC_0001, TC_0002, TC_0003] {
+    let expected = parse_from_string(content).unwrap();
+    let written = write_to_string(&expected);
+    let actual = parse_from_string(&written).unwrap();
+    assert_eq!(expected, actual);
+    assert_eq!(written, write_to_string(&actual));
+  }
+}
+
+#[test]
+fn test_round_trip_special_values() {
+  let expected = TestCases {
+    model_name: Some("special <&> values.dmn".to_string()),
+    labels: vec![],
+    test_cases: vec![TestCase {
+      id: Some("\"001\"".to_string()),
+      name: Some("special".to_string()),
+      typ: TestCaseType::DecisionService,
+      description: None,
+      invocable_name: Some("service".to_string()),
+      input_nodes: vec![],
+      result_nodes: vec![ResultNode {
+        name: "result".to_string(),
+        error_result: true,
+        typ: TestCaseType::DecisionService,
+        cast: Some("xsd:decimal".to_string()),
+        expected: Some(Value::Components(vec![
+          Component {
+            name: Some("a".to_string()),
+            value: None,
+            nil: true,
+          },
+          Component {
+            name: Some("b".to_string()),
+            value: Some(Value::List(List::default())),
+            nil: false,
+          },
+          Component {
+            name: Some("c".to_string()),
+            value: Some(Value::List(List {
+              items: vec![],
+              nil: false,
+            })),
+            nil: false,
+          },
+          Component {
+            name: Some("d".to_string()),
+            value: Some(Value::Simple(Simple {
+              typ: Some("xsd:string".to_string()),
+              text: Some("".to_string()),
+              nil: false,
+            })),
+            nil: false,
+          },
+          Component {
+            name: Some("e".to_string()),
+            value: Some(Value::Simple(Simple {
+              typ: None,
+              text: None,
+              nil: true,
+            })),
+            nil: false,
+          },
+        ])),
+        computed: Some(Value::Simple(Simple {
+          typ: Some("xsd:string".to_string()),
+          text: Some("a < b && c > d".to_string()),
+          nil: false,
+        })),
+      }],
+    }],
+  };
+  let actual = parse_from_string(&write_to_string(&expected)).unwrap();
+  assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_written_file_is_valid() {
+  let file_name = std::env::temp_dir().join(format!("dmn-tck-rs-writer-0003-{}.xml", std::process::id()));
+  let file_name = file_name.to_str().unwrap();
+  write_to_file(&parse_from_string(TC_0003).unwrap(), file_name).unwrap();
+  assert_eq!(Ok(()), validate_test_cases_file(file_name));
+  std::fs::remove_file(file_name).unwrap();
+}
@@ -17,11 +17,11 @@
 //! Test case file validator.
 //!
 //! This validator is based on the code developed by Franklin Chen in project
-//! https://github.com/FranklinChen/validate-xml-rust. Thanks a lot Franklin!
+//! <https://github.com/FranklinChen/validate-xml-rust>. Thanks a lot Franklin!
 //!
 //! To be able to link with `libxml2` library, the development version
 //! must be installed, which on Fedora may be done this way:
-//! ```
+//! ```text
 //! sudo yum install libxml2-devel
 //! ```
 
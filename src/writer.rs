@@ -0,0 +1,238 @@
+/*
+ *  Copyright 2020 Dariusz Depta Engos Software
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Writer for XML files containing definitions of test cases.
+//!
+//! The output is formatted in a stable way (four spaces indentation, fixed order
+//! of elements and attributes), so parsing the written file results in the same model.
+//! Types of simple values are expected to be prefixed with `xsd:`, as in TCK files.
+
+use crate::errors::RunnerError;
+use crate::model::{Component, InputNode, List, ResultNode, Simple, TestCase, TestCaseType, TestCases, Value};
+use std::fs;
+
+const INDENT: &str = "    ";
+
+/// Writes test cases to XML file.
+pub fn write_to_file(test_cases: &TestCases, file_name: &str) -> Result<(), RunnerError> {
+  fs::write(file_name, write_to_string(test_cases))?;
+  Ok(())
+}
+
+/// Writes test cases to XML string.
+pub fn write_to_string(test_cases: &TestCases) -> String {
+  let mut w = XmlWriter::default();
+  w.line(0, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+  w.line(0, r#"<testCases xmlns="http://www.omg.org/spec/DMN/20160719/testcase""#);
+  w.line(0, r#"           xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance""#);
+  w.line(0, r#"           xmlns:xsd="http://www.w3.org/2001/XMLSchema">"#);
+  if let Some(model_name) = &test_cases.model_name {
+    w.line(1, &format!("<modelName>{}</modelName>", escape(model_name)));
+  }
+  if !test_cases.labels.is_empty() {
+    w.line(1, "<labels>");
+    for label in &test_cases.labels {
+      w.line(2, &format!("<label>{}</label>", escape(label)));
+    }
+    w.line(1, "</labels>");
+  }
+  for test_case in &test_cases.test_cases {
+    write_test_case(&mut w, test_case);
+  }
+  w.line(0, "</testCases>");
+  w.output
+}
+
+/// Writes single test case.
+fn write_test_case(w: &mut XmlWriter, test_case: &TestCase) {
+  let mut attributes = vec![];
+  if let Some(id) = &test_case.id {
+    attributes.push(("id", id.clone()));
+  }
+  if let Some(name) = &test_case.name {
+    attributes.push(("name", name.clone()));
+  }
+  if test_case.typ != TestCaseType::Decision {
+    attributes.push(("type", test_case.typ.to_string()));
+  }
+  if let Some(invocable_name) = &test_case.invocable_name {
+    attributes.push(("invocableName", invocable_name.clone()));
+  }
+  w.line(1, &format!("<testCase{}>", format_attributes(&attributes)));
+  if let Some(description) = &test_case.description {
+    w.line(2, &format!("<description>{}</description>", escape(description)));
+  }
+  for input_node in &test_case.input_nodes {
+    write_input_node(w, input_node);
+  }
+  for result_node in &test_case.result_nodes {
+    write_result_node(w, result_node);
+  }
+  w.line(1, "</testCase>");
+}
+
+/// Writes input node.
+fn write_input_node(w: &mut XmlWriter, input_node: &InputNode) {
+  let attributes = vec![("name", input_node.name.clone())];
+  write_value_element(w, 2, "inputNode", &attributes, input_node.value.as_ref(), false);
+}
+
+/// Writes result node, `computed` value is written before `expected` value as required by schema.
+fn write_result_node(w: &mut XmlWriter, result_node: &ResultNode) {
+  let mut attributes = vec![("name", result_node.name.clone())];
+  if result_node.error_result {
+    attributes.push(("errorResult", "true".to_string()));
+  }
+  attributes.push(("type", result_node.typ.to_string()));
+  if let Some(cast) = &result_node.cast {
+    attributes.push(("cast", cast.clone()));
+  }
+  if result_node.computed.is_none() && result_node.expected.is_none() {
+    w.line(2, &format!("<resultNode{}/>", format_attributes(&attributes)));
+    return;
+  }
+  w.line(2, &format!("<resultNode{}>", format_attributes(&attributes)));
+  if let Some(computed) = &result_node.computed {
+    write_value_element(w, 3, "computed", &[], Some(computed), false);
+  }
+  if let Some(expected) = &result_node.expected {
+    write_value_element(w, 3, "expected", &[], Some(expected), false);
+  }
+  w.line(2, "</resultNode>");
+}
+
+/// Writes an element of type `valueType` with optional value as its content.
+fn write_value_element(
+  w: &mut XmlWriter,
+  level: usize,
+  tag: &str,
+  attributes: &[(&str, String)],
+  value: Option<&Value>,
+  nil: bool,
+) {
+  let mut attributes = attributes.to_vec();
+  if nil {
+    attributes.push(("xsi:nil", "true".to_string()));
+  }
+  if let Some(value) = value {
+    w.line(level, &format!("<{}{}>", tag, format_attributes(&attributes)));
+    write_value(w, level + 1, value);
+    w.line(level, &format!("</{}>", tag));
+  } else {
+    w.line(level, &format!("<{}{}/>", tag, format_attributes(&attributes)));
+  }
+}
+
+/// Writes value content.
+fn write_value(w: &mut XmlWriter, level: usize, value: &Value) {
+  match value {
+    Value::Simple(simple) => write_simple(w, level, simple),
+    Value::Components(components) => {
+      for component in components {
+        write_component(w, level, component);
+      }
+    }
+    Value::List(list) => write_list(w, level, list),
+  }
+}
+
+/// Writes simple value.
+fn write_simple(w: &mut XmlWriter, level: usize, simple: &Simple) {
+  let mut attributes = vec![];
+  if let Some(typ) = &simple.typ {
+    attributes.push(("xsi:type", typ.clone()));
+  }
+  if simple.nil {
+    attributes.push(("xsi:nil", "true".to_string()));
+  }
+  match &simple.text {
+    Some(text) if !text.is_empty() => w.line(
+      level,
+      &format!("<value{}>{}</value>", format_attributes(&attributes), escape(text)),
+    ),
+    _ => w.line(level, &format!("<value{}/>", format_attributes(&attributes))),
+  }
+}
+
+/// Writes component value.
+fn write_component(w: &mut XmlWriter, level: usize, component: &Component) {
+  let mut attributes = vec![];
+  if let Some(name) = &component.name {
+    attributes.push(("name", name.clone()));
+  }
+  write_value_element(
+    w,
+    level,
+    "component",
+    &attributes,
+    component.value.as_ref(),
+    component.nil,
+  );
+}
+
+/// Writes list value.
+fn write_list(w: &mut XmlWriter, level: usize, list: &List) {
+  if list.nil {
+    w.line(level, r#"<list xsi:nil="true"/>"#);
+  } else if list.items.is_empty() {
+    w.line(level, "<list/>");
+  } else {
+    w.line(level, "<list>");
+    for item in &list.items {
+      write_value_element(w, level + 1, "item", &[], Some(item), false);
+    }
+    w.line(level, "</list>");
+  }
+}
+
+/// Formats attributes, each preceded with a single space.
+fn format_attributes(attributes: &[(&str, String)]) -> String {
+  attributes
+    .iter()
+    .map(|(name, value)| format!(r#" {}="{}""#, name, escape_attribute(value)))
+    .collect()
+}
+
+/// Escapes special characters in textual content.
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Escapes special characters in attribute value.
+fn escape_attribute(text: &str) -> String {
+  escape(text)
+    .replace('"', "&quot;")
+    .replace('\t', "&#9;")
+    .replace('\n', "&#10;")
+    .replace('\r', "&#13;")
+}
+
+/// Simple line-oriented XML writer.
+#[derive(Default)]
+struct XmlWriter {
+  output: String,
+}
+
+impl XmlWriter {
+  /// Appends a single line indented to specified level.
+  fn line(&mut self, level: usize, content: &str) {
+    for _ in 0..level {
+      self.output.push_str(INDENT);
+    }
+    self.output.push_str(content);
+    self.output.push('\n');
+  }
+}